@@ -0,0 +1,141 @@
+//! A global allocator that can count what each step of a solution allocates.
+//! A binary installs it with
+//!
+//! ```ignore
+//! #[global_allocator]
+//! static ALLOCATOR: aoc_common::alloc::CountingAlloc = aoc_common::alloc::CountingAlloc;
+//! ```
+//!
+//! and nothing is counted until [`enable`] is called, so an installed but
+//! disabled allocator costs one atomic load per allocation.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+/// What one step allocated. All zero when counting is off.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Allocs {
+    /// Number of allocations, including reallocations.
+    pub count: u64,
+    /// Bytes requested by those allocations.
+    pub bytes: u64,
+    /// Most heap the step held at once, beyond what was live when it began.
+    pub peak: u64,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COUNT: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn allocated(size: usize) {
+        if ENABLED.load(Ordering::Relaxed) {
+            COUNT.fetch_add(1, Ordering::Relaxed);
+            BYTES.fetch_add(size as u64, Ordering::Relaxed);
+            let live = LIVE.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+            PEAK.fetch_max(live, Ordering::Relaxed);
+        }
+    }
+
+    fn freed(size: usize) {
+        if ENABLED.load(Ordering::Relaxed) {
+            // Memory allocated before counting began may be freed after, so
+            // live bytes stop at zero.
+            let _ = LIVE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| {
+                Some(live.saturating_sub(size as u64))
+            });
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::allocated(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::allocated(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        Self::freed(layout.size());
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::freed(layout.size());
+        Self::allocated(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Starts or stops counting. Only useful with [`CountingAlloc`] installed.
+pub fn enable(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f` and reports what it allocated. Counts are process wide, so other
+/// threads allocating at the same time are counted too.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Allocs) {
+    if !enabled() {
+        return (f(), Allocs::default());
+    }
+    let count = COUNT.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+    let value = f();
+    let allocs = Allocs {
+        count: COUNT.load(Ordering::Relaxed) - count,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(live),
+    };
+    (value, allocs)
+}
+
+/// Bytes in B, KiB, MiB or GiB, whichever reads best.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes}B")
+    } else {
+        format!("{value:.1}{}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_is_measured_while_counting_is_off() {
+        let (v, allocs) = measure(|| vec![1_u8; 100]);
+        assert_eq!(v.len(), 100);
+        assert_eq!(allocs, Allocs::default());
+    }
+
+    #[test]
+    fn byte_counts_use_binary_units() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0MiB");
+    }
+}
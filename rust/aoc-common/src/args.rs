@@ -0,0 +1,116 @@
+//! A deliberately small command line parser for any mix of `--option value`
+//! pairs, bare `--flag`s and positional arguments.
+
+use std::str::FromStr;
+
+#[derive(Debug, Default)]
+pub struct Args {
+    options: Vec<(String, Option<String>)>,
+    pub positionals: Vec<String>,
+}
+
+impl Args {
+    /// Options named in `takes_value` consume the argument that follows them
+    /// (or the text after `=` when written as `--option=value`).
+    pub fn parse<I>(args: I, takes_value: &[&str]) -> Result<Args, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter();
+        let mut parsed = Args::default();
+        while let Some(arg) = args.next() {
+            if arg == "-" || !arg.starts_with("--") {
+                parsed.positionals.push(arg);
+                continue;
+            }
+
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            let value = if takes_value.contains(&name.as_str()) {
+                let value = inline_value
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("Option `{name}` needs a value"))?;
+                Some(value)
+            } else if inline_value.is_some() {
+                return Err(format!("Option `{name}` does not take a value"));
+            } else {
+                None
+            };
+            parsed.options.push((name, value));
+        }
+        Ok(parsed)
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.iter().any(|(n, _)| n == name)
+    }
+
+    /// The last value given for `name`, so later options override earlier ones.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+
+    pub fn parsed_value<T>(&self, name: &str) -> Result<Option<T>, String>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.value(name)
+            .map(|v| {
+                v.parse::<T>()
+                    .map_err(|err| format!("Invalid value `{v}` for `{name}`: {err}"))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Result<Args, String> {
+        Args::parse(line.split_whitespace().map(String::from), &["--day"])
+    }
+
+    #[test]
+    fn value_options_consume_the_next_argument() {
+        let args = parse("--day 7 input.txt").unwrap();
+        assert_eq!(args.value("--day"), Some("7"));
+        assert_eq!(args.positionals, ["input.txt"]);
+    }
+
+    #[test]
+    fn value_options_can_be_joined_with_equals() {
+        let args = parse("--day=7").unwrap();
+        assert_eq!(args.parsed_value::<u8>("--day"), Ok(Some(7)));
+    }
+
+    #[test]
+    fn a_value_option_at_the_end_is_an_error() {
+        assert!(parse("--day").is_err());
+    }
+
+    #[test]
+    fn flags_do_not_consume_arguments() {
+        let args = parse("--all inputs").unwrap();
+        assert!(args.flag("--all"));
+        assert_eq!(args.positionals, ["inputs"]);
+    }
+
+    #[test]
+    fn a_single_dash_is_a_positional() {
+        assert_eq!(parse("-").unwrap().positionals, ["-"]);
+    }
+
+    #[test]
+    fn unparsable_values_are_reported() {
+        let args = parse("--day seven").unwrap();
+        assert!(args.parsed_value::<u8>("--day").is_err());
+    }
+}
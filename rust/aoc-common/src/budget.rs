@@ -0,0 +1,163 @@
+//! Time budgets for solving a part. A runner sets a budget with [`limit`],
+//! [`solve`](crate::solve) restarts the clock before every part, and
+//! long-running loops in the solvers call [`check`], which abandons the part
+//! once its time is up. Cancellation is cooperative: a loop without a check
+//! runs to the end.
+//!
+//! Budgets are per thread, for the thread that solves the input.
+
+use std::{
+    cell::Cell,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+/// A part that ran out of time, with the progress its loop reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timeout {
+    pub part: u8,
+    pub budget: Duration,
+    pub progress: String,
+}
+
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Part {} timed out after {:?} ({})",
+            self.part, self.budget, self.progress
+        )
+    }
+}
+
+struct Clock {
+    budget: Duration,
+    part: u8,
+    deadline: Instant,
+}
+
+thread_local! {
+    static CLOCK: Cell<Option<Clock>> = const { Cell::new(None) };
+}
+
+/// Runs `f` giving every part it solves `budget`, or as long as it takes with
+/// no budget. A part that runs out of time becomes an error.
+pub fn limit<R>(budget: Option<Duration>, f: impl FnOnce() -> R) -> Result<R, Timeout> {
+    let Some(budget) = budget else {
+        return Ok(f());
+    };
+    let previous = CLOCK.replace(Some(Clock {
+        budget,
+        part: 0,
+        deadline: Instant::now() + budget,
+    }));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CLOCK.set(previous);
+    result.or_else(|payload| match payload.downcast::<Timeout>() {
+        Ok(timeout) => Err(*timeout),
+        Err(payload) => panic::resume_unwind(payload),
+    })
+}
+
+/// Restarts the clock for `part`, if there is a budget.
+pub fn start_part(part: u8) {
+    CLOCK.with(|clock| {
+        if let Some(current) = clock.take() {
+            clock.set(Some(Clock {
+                part,
+                deadline: Instant::now() + current.budget,
+                ..current
+            }));
+        }
+    });
+}
+
+/// Abandons the current part if it has run out of time, reporting
+/// `progress()` as how far it got. Cheap enough to call on every iteration
+/// of a loop.
+pub fn check(progress: impl FnOnce() -> String) {
+    let expired = CLOCK.with(|clock| {
+        let current = clock.take()?;
+        let expired = Instant::now() >= current.deadline;
+        let timeout = expired.then_some((current.part, current.budget));
+        clock.set(Some(current));
+        timeout
+    });
+    if let Some((part, budget)) = expired {
+        // Unwinding without panicking keeps the panic hook quiet; `limit`
+        // turns this back into an error.
+        panic::resume_unwind(Box::new(Timeout {
+            part,
+            budget,
+            progress: progress(),
+        }));
+    }
+}
+
+/// Reads a budget given in seconds on the command line.
+pub fn parse_seconds(secs: f64) -> Result<Duration, String> {
+    Duration::try_from_secs_f64(secs)
+        .ok()
+        .filter(|budget| !budget.is_zero())
+        .ok_or_else(|| format!("A budget must be a positive number of seconds, not {secs}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spin(steps: &mut u64) {
+        loop {
+            *steps += 1;
+            check(|| format!("{steps} steps"));
+        }
+    }
+
+    #[test]
+    fn a_part_that_runs_out_of_time_is_a_timeout() {
+        let mut steps = 0;
+        let timeout = limit(Some(Duration::from_millis(10)), || {
+            start_part(2);
+            spin(&mut steps)
+        })
+        .unwrap_err();
+        assert_eq!(timeout.part, 2);
+        assert_eq!(timeout.progress, format!("{steps} steps"));
+        assert!(timeout
+            .to_string()
+            .starts_with("Part 2 timed out after 10ms"));
+    }
+
+    #[test]
+    fn checks_do_nothing_without_a_budget() {
+        check(|| unreachable!("No budget, so no progress is asked for"));
+        assert_eq!(limit(None, || 42), Ok(42));
+    }
+
+    #[test]
+    fn each_part_gets_the_whole_budget() {
+        let result = limit(Some(Duration::from_millis(200)), || {
+            for part in 1..=2 {
+                start_part(part);
+                std::thread::sleep(Duration::from_millis(120));
+                check(|| "sleeping".to_string());
+            }
+            "done"
+        });
+        assert_eq!(result, Ok("done"));
+    }
+
+    #[test]
+    fn other_panics_are_not_timeouts() {
+        let result = panic::catch_unwind(|| limit(Some(Duration::from_secs(1)), || panic!("boom")));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn budgets_are_positive_seconds() {
+        assert_eq!(parse_seconds(1.5), Ok(Duration::from_millis(1500)));
+        assert!(parse_seconds(0.0).is_err());
+        assert!(parse_seconds(-1.0).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+//! Puzzle examples kept as files next to each day: `examples/<name>.txt`
+//! holds the input and `examples/<name>.expected` the answers, one
+//! `part_NN: answer` line per part the example covers. A day's build script
+//! calls [`generate_example_tests`] and its lib includes the result, so every
+//! example becomes its own test.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+use crate::{solve, Solution};
+
+/// Reads the `part_NN: answer` lines of an `.expected` file.
+fn expected_answers(expected: &str) -> Result<Vec<(u8, String)>, String> {
+    expected
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (part, answer) = line
+                .split_once(':')
+                .ok_or_else(|| format!("Expected `part_NN: answer`, found `{line}`"))?;
+            let part = part
+                .trim()
+                .strip_prefix("part_")
+                .and_then(|n| n.parse::<u8>().ok())
+                .filter(|n| (1..=2).contains(n))
+                .ok_or_else(|| format!("Unknown part `{}`; use part_01 or part_02", part.trim()))?;
+            Ok((part, answer.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Solves `input` and checks every answer listed in `expected`.
+///
+/// # Panics
+///
+/// If the input does not parse, `expected` is malformed or an answer differs.
+pub fn check_example<S: Solution>(input: &str, expected: &str) {
+    let expected = expected_answers(expected).unwrap_or_else(|msg| panic!("{msg}"));
+    assert!(!expected.is_empty(), "The .expected file lists no answers");
+    let parts = expected.iter().map(|(part, _)| *part).collect::<Vec<_>>();
+    let answers = solve::<S>(input, &parts).unwrap_or_else(|err| panic!("{}", err.render()));
+    for ((part, want), got) in expected.iter().zip(&answers.parts) {
+        assert_eq!(&got.answer, want, "part {part} answer");
+    }
+}
+
+/// For a day's build script: writes `examples.rs` to `OUT_DIR` with one test
+/// per `examples/*.txt` in the package, named after the file.
+///
+/// # Panics
+///
+/// If an example has no `.expected` file or the output cannot be written.
+pub fn generate_example_tests() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("Run from a build script");
+    let out_dir = env::var("OUT_DIR").expect("Run from a build script");
+    let package = env::var("CARGO_PKG_NAME").expect("Run from a build script");
+    let examples = Path::new(&manifest_dir).join("examples");
+    println!("cargo:rerun-if-changed={}", examples.display());
+
+    let solution = format!("crate::Day{:02}", crate::day_number(&package));
+    let mut inputs = fs::read_dir(&examples)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    inputs.sort();
+
+    let mut tests = String::new();
+    for input in inputs {
+        let expected = input.with_extension("expected");
+        assert!(
+            expected.is_file(),
+            "{} has no matching {}",
+            input.display(),
+            expected.display()
+        );
+        let name = input
+            .file_stem()
+            .expect("Has a .txt extension")
+            .to_string_lossy()
+            .replace(|c: char| !c.is_ascii_alphanumeric(), "_")
+            .to_lowercase();
+        let name = if name.starts_with(|c: char| c.is_ascii_digit()) {
+            format!("example_{name}")
+        } else {
+            name
+        };
+        writeln!(
+            tests,
+            "#[test]\nfn {name}() {{\n    \
+             aoc_common::check_example::<{solution}>(include_str!({input:?}), include_str!({expected:?}));\n}}\n"
+        )
+        .expect("Writing to a String cannot fail");
+    }
+    fs::write(Path::new(&out_dir).join("examples.rs"), tests).expect("Can write to OUT_DIR");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_files_list_answers_by_part() {
+        assert_eq!(
+            expected_answers("part_01: 142\n\npart_02: 281\n"),
+            Ok(vec![(1, "142".to_string()), (2, "281".to_string())])
+        );
+    }
+
+    #[test]
+    fn unknown_parts_are_rejected() {
+        assert!(expected_answers("part_03: 1").is_err());
+        assert!(expected_answers("142").is_err());
+    }
+}
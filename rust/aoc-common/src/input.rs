@@ -0,0 +1,159 @@
+//! Where puzzle inputs come from: files, every file in a directory, or stdin
+//! when the path is `-`.
+
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::{csv_summary, print_answers, AOCErr, Answers, Format};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    /// `-` means stdin; anything else is a path.
+    pub fn from_arg(arg: &str) -> Input {
+        match arg {
+            "-" => Input::Stdin,
+            path => Input::File(PathBuf::from(path)),
+        }
+    }
+
+    /// How the input is named in reports: its path, or `-` for stdin.
+    pub fn name(&self) -> String {
+        match self {
+            Input::Stdin => "-".to_string(),
+            Input::File(path) => path.display().to_string(),
+        }
+    }
+
+    pub fn read(&self) -> Result<String, AOCErr> {
+        match self {
+            Input::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(AOCErr::CannotReadFile)?;
+                Ok(input)
+            }
+            Input::File(path) => crate::load_input(path),
+        }
+    }
+}
+
+/// The inputs named by command line arguments, in order. A directory stands
+/// for every file directly inside it, sorted by name.
+pub fn expand_inputs(args: &[String]) -> Result<Vec<Input>, AOCErr> {
+    let mut inputs = Vec::new();
+    for arg in args {
+        let input = Input::from_arg(arg);
+        match &input {
+            Input::File(dir) if dir.is_dir() => {
+                let mut files = fs::read_dir(dir)
+                    .map_err(AOCErr::CannotReadFile)?
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| path.is_file())
+                    .collect::<Vec<_>>();
+                files.sort();
+                inputs.extend(files.into_iter().map(Input::File));
+            }
+            _ => inputs.push(input),
+        }
+    }
+    Ok(inputs)
+}
+
+/// Solves every input and prints its answers, preceded by the input's name
+/// in text format when there is more than one. A failing input is reported
+/// and the rest still run. With `csv`, a summary of every answer is written to
+/// that path.
+pub fn run_inputs(
+    inputs: &[Input],
+    solve: &dyn Fn(&str) -> Result<Answers, String>,
+    format: Format,
+    csv: Option<&str>,
+) -> Result<(), String> {
+    if let [input] = inputs {
+        if csv.is_none() {
+            let text = input.read().map_err(|err| crate::err_msg(&err, "aoc"))?;
+            print_answers(&solve(&text)?, format);
+            return Ok(());
+        }
+    }
+
+    let mut results = Vec::new();
+    let mut failures = 0;
+    for input in inputs {
+        let name = input.name();
+        if inputs.len() > 1 && format == Format::Text {
+            println!("== {name}");
+        }
+        let answers = input
+            .read()
+            .map_err(|err| crate::err_msg(&err, "aoc"))
+            .and_then(|text| solve(&text));
+        match answers {
+            Ok(answers) => {
+                print_answers(&answers, format);
+                results.push((name, answers));
+            }
+            Err(msg) => {
+                eprintln!("ERROR {name}: {msg}");
+                failures += 1;
+            }
+        }
+    }
+
+    if let Some(path) = csv {
+        fs::write(path, csv_summary(&results))
+            .map_err(|err| format!("Could not write {path}: {err}"))?;
+    }
+    if failures > 0 {
+        return Err(format!("{failures} of {} inputs failed", inputs.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    #[test]
+    fn a_dash_means_stdin() {
+        assert_eq!(Input::from_arg("-"), Input::Stdin);
+        assert_eq!(Input::Stdin.name(), "-");
+    }
+
+    #[test]
+    fn directories_expand_to_their_files_in_order() {
+        let dir = env::temp_dir().join(format!("aoc-expand-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        fs::write(dir.join("b.txt"), "").unwrap();
+        fs::write(dir.join("a.txt"), "").unwrap();
+
+        let args = ["-".to_string(), dir.display().to_string()];
+        assert_eq!(
+            expand_inputs(&args).unwrap(),
+            [
+                Input::Stdin,
+                Input::File(dir.join("a.txt")),
+                Input::File(dir.join("b.txt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_files_are_kept_so_they_can_be_reported() {
+        let args = ["does/not/exist.txt".to_string()];
+        let inputs = expand_inputs(&args).unwrap();
+        assert!(matches!(inputs[0].read(), Err(AOCErr::CannotReadFile(_))));
+    }
+}
@@ -0,0 +1,174 @@
+//! Pieces shared by every day binary: reading the puzzle input named on the
+//! command line, reporting errors and printing results.
+
+pub mod alloc;
+pub mod args;
+pub mod budget;
+mod examples;
+mod input;
+mod output;
+mod parse;
+pub mod repl;
+mod solution;
+pub mod trace;
+
+use std::{env, fs, io, path::Path, process::ExitCode};
+
+use args::Args;
+pub use examples::{check_example, generate_example_tests};
+pub use input::{expand_inputs, run_inputs, Input};
+pub use output::{csv_summary, format_answers, json_escape, result_label, Format};
+pub use parse::{ParseError, SpanError};
+pub use solution::{
+    crosscheck, solve, Alternate, Answers, CrossCheck, Implementation, PartAnswer, Solution,
+    SolveError,
+};
+
+pub const YEAR: u32 = 2023;
+
+/// The day number in a `day-NN` package name. Days use it as
+/// `aoc_common::day_number(env!("CARGO_PKG_NAME"))` so their labels always
+/// match the crate they live in; any other name fails to compile.
+pub const fn day_number(package_name: &str) -> u8 {
+    match package_name.as_bytes() {
+        [b'd', b'a', b'y', b'-', tens @ b'0'..=b'2', ones @ b'0'..=b'9'] => {
+            let day = (*tens - b'0') * 10 + (*ones - b'0');
+            assert!(
+                1 <= day && day <= 25,
+                "Advent of Code days run from 1 to 25"
+            );
+            day
+        }
+        _ => panic!("Day packages must be named `day-NN`"),
+    }
+}
+
+#[derive(Debug)]
+pub enum AOCErr {
+    NoInputProvided,
+    CannotReadFile(io::Error),
+}
+
+pub fn err_msg(err: &AOCErr, program: &str) -> String {
+    match err {
+        AOCErr::NoInputProvided => {
+            format!(
+                "Usage: {program} [--format text|json|tsv] [--csv <summary.csv>] [--trace text|json] [--budget <SECS>] <input_filename|dir|->..."
+            )
+        }
+        AOCErr::CannotReadFile(reason) => format!("Could not read input: {reason}"),
+    }
+}
+
+pub fn load_input(path: impl AsRef<Path>) -> Result<String, AOCErr> {
+    fs::read_to_string(path).map_err(AOCErr::CannotReadFile)
+}
+
+/// Reads the input named by the first positional argument, which may be `-`
+/// for stdin.
+pub fn read_input(args: &Args) -> Result<String, AOCErr> {
+    args.positionals
+        .first()
+        .ok_or(AOCErr::NoInputProvided)
+        .and_then(|arg| Input::from_arg(arg).read())
+}
+
+pub fn print_answers(answers: &Answers, format: Format) {
+    for line in format_answers(answers, format) {
+        println!("{line}");
+    }
+}
+
+/// Entry point for a day binary. Reads each input named on the command line
+/// (files, directories of files, or `-` for stdin), prints the result of both
+/// parts for each and exits non-zero if any input could not be loaded or
+/// parsed. `--trace` writes the solution's trace events to stderr, and
+/// `--budget` gives each part that many seconds before it is reported as
+/// timed out.
+pub fn run<S: Solution>() -> ExitCode {
+    let mut args = env::args();
+    let program = args.next().unwrap_or_else(|| "aoc".to_string());
+
+    let result =
+        Args::parse(args, &["--format", "--csv", "--trace", "--budget"]).and_then(|args| {
+            let format = args.parsed_value::<Format>("--format")?.unwrap_or_default();
+            if let Some(trace_format) = args.parsed_value::<trace::TraceFormat>("--trace")? {
+                trace::enable(trace_format);
+            }
+            let budget = args
+                .parsed_value::<f64>("--budget")?
+                .map(budget::parse_seconds)
+                .transpose()?;
+            let inputs = expand_inputs(&args.positionals).map_err(|err| err_msg(&err, &program))?;
+            if inputs.is_empty() {
+                return Err(err_msg(&AOCErr::NoInputProvided, &program));
+            }
+            if let Some(header) = format.header() {
+                println!("{header}");
+            }
+            run_inputs(
+                &inputs,
+                &|input| {
+                    budget::limit(budget, || solve::<S>(input, &[1, 2]))
+                        .map_err(|timeout| timeout.to_string())?
+                        .map_err(|err| err.render())
+                },
+                format,
+                args.value("--csv"),
+            )
+        });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("ERROR {msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::parse(args.iter().map(|s| s.to_string()), &["--format"]).unwrap()
+    }
+
+    #[test]
+    fn day_numbers_come_from_package_names() {
+        assert_eq!(day_number("day-07"), 7);
+        assert_eq!(day_number("day-25"), 25);
+    }
+
+    #[test]
+    #[should_panic]
+    fn package_names_must_name_a_day() {
+        day_number("aoc-common");
+    }
+
+    #[test]
+    #[should_panic]
+    fn day_numbers_stop_at_25() {
+        day_number("day-26");
+    }
+
+    #[test]
+    fn missing_input_argument_is_an_error() {
+        let args = args(&["--format", "json"]);
+        assert!(matches!(read_input(&args), Err(AOCErr::NoInputProvided)));
+    }
+
+    #[test]
+    fn unreadable_input_file_is_an_error() {
+        let args = args(&["does/not/exist.txt"]);
+        assert!(matches!(read_input(&args), Err(AOCErr::CannotReadFile(_))));
+    }
+
+    #[test]
+    fn usage_message_names_the_program() {
+        assert_eq!(
+            err_msg(&AOCErr::NoInputProvided, "day-01"),
+            "Usage: day-01 [--format text|json|tsv] [--csv <summary.csv>] [--trace text|json] [--budget <SECS>] <input_filename|dir|->..."
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Renders answers as the human readable labels the day binaries have always
+//! printed, or as one record per part for scripts (JSON lines or TSV).
+
+use std::{fmt::Write, str::FromStr};
+
+use crate::{Answers, YEAR};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, <Format as FromStr>::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "tsv" => Ok(Format::Tsv),
+            other => Err(format!("Unknown format `{other}`; use text, json or tsv")),
+        }
+    }
+}
+
+impl Format {
+    /// Line to print once before any records, if the format has one.
+    pub fn header(&self) -> Option<&'static str> {
+        match self {
+            Format::Tsv => Some("year\tday\tpart\tanswer\tparse_ns\tsolve_ns"),
+            Format::Text | Format::Json => None,
+        }
+    }
+}
+
+pub fn result_label(day: u8, part: u8) -> String {
+    format!("[advent-of-code-{YEAR}:day_{day:02}:part_{part:02}]")
+}
+
+/// Escapes `s` as the contents of a JSON string.
+pub fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => {
+                write!(escaped, "\\u{:04x}", c as u32).expect("Writing to a String cannot fail")
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// One line per answered part. Parse time is shared by every part of a run.
+pub fn format_answers(answers: &Answers, format: Format) -> Vec<String> {
+    let parse_ns = answers.parse_time.as_nanos();
+    answers
+        .parts
+        .iter()
+        .map(|part| {
+            let solve_ns = part.elapsed.as_nanos();
+            match format {
+                Format::Text => format!("{} {}", result_label(answers.day, part.part), part.answer),
+                Format::Json => format!(
+                    "{{\"year\":{YEAR},\"day\":{},\"part\":{},\"answer\":\"{}\",\
+                     \"parse_ns\":{parse_ns},\"solve_ns\":{solve_ns}}}",
+                    answers.day,
+                    part.part,
+                    json_escape(&part.answer),
+                ),
+                Format::Tsv => format!(
+                    "{YEAR}\t{}\t{}\t{}\t{parse_ns}\t{solve_ns}",
+                    answers.day,
+                    part.part,
+                    part.answer.replace(['\t', '\n'], " "),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Quotes a CSV field when it holds a comma, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// One `input,day,part,answer` row per answered part of every input, so
+/// answers for different inputs can be compared side by side.
+pub fn csv_summary(results: &[(String, Answers)]) -> String {
+    let mut csv = String::from("input,day,part,answer\n");
+    for (input, answers) in results {
+        for part in &answers.parts {
+            writeln!(
+                csv,
+                "{},{},{},{}",
+                csv_field(input),
+                answers.day,
+                part.part,
+                csv_field(&part.answer)
+            )
+            .expect("Writing to a String cannot fail");
+        }
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{alloc::Allocs, PartAnswer};
+
+    fn answers() -> Answers {
+        Answers {
+            day: 7,
+            parse_time: Duration::from_nanos(1500),
+            parse_allocs: Allocs::default(),
+            parts: vec![PartAnswer {
+                part: 2,
+                answer: "5905".to_string(),
+                elapsed: Duration::from_nanos(250),
+                allocs: Allocs::default(),
+            }],
+        }
+    }
+
+    #[test]
+    fn result_labels_are_zero_padded() {
+        assert_eq!(result_label(7, 2), "[advent-of-code-2023:day_07:part_02]");
+    }
+
+    #[test]
+    fn formats_can_be_parsed_by_name() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert!("xml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn text_output_uses_the_result_label() {
+        assert_eq!(
+            format_answers(&answers(), Format::Text),
+            ["[advent-of-code-2023:day_07:part_02] 5905"]
+        );
+    }
+
+    #[test]
+    fn json_output_is_one_object_per_part() {
+        assert_eq!(
+            format_answers(&answers(), Format::Json),
+            [r#"{"year":2023,"day":7,"part":2,"answer":"5905","parse_ns":1500,"solve_ns":250}"#]
+        );
+    }
+
+    #[test]
+    fn tsv_output_matches_its_header() {
+        let header_columns = Format::Tsv.header().unwrap().split('\t').count();
+        let record = &format_answers(&answers(), Format::Tsv)[0];
+        assert_eq!(record, "2023\t7\t2\t5905\t1500\t250");
+        assert_eq!(record.split('\t').count(), header_columns);
+    }
+
+    #[test]
+    fn csv_summary_has_a_row_per_input_and_part() {
+        let results = [
+            ("alice.txt".to_string(), answers()),
+            ("bob, jr.txt".to_string(), answers()),
+        ];
+        assert_eq!(
+            csv_summary(&results),
+            "input,day,part,answer\nalice.txt,7,2,5905\n\"bob, jr.txt\",7,2,5905\n"
+        );
+    }
+
+    #[test]
+    fn json_strings_are_escaped() {
+        assert_eq!(json_escape("a\"b\\c\nd\u{1}"), "a\\\"b\\\\c\\nd\\u0001");
+    }
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+
+/// A parse failure that points at the slice of input it is about.
+///
+/// Parsers only ever see pieces of the puzzle input (a line, a token, one
+/// block of a multi-part format), so rather than threading offsets through
+/// every parser the error remembers where the offending slice lives in
+/// memory. [`SpanError::locate`] later works out the line and column by
+/// finding that slice inside the full input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanError {
+    addr: usize,
+    len: usize,
+    pub message: String,
+}
+
+impl SpanError {
+    /// `part` should be a slice of the text being parsed, not a copy of it.
+    pub fn at(part: &str, message: impl Into<String>) -> SpanError {
+        SpanError {
+            addr: part.as_ptr() as usize,
+            len: part.len(),
+            message: message.into(),
+        }
+    }
+
+    /// Places the error within `input`. Errors whose slice did not come from
+    /// `input` are reported against its first line.
+    pub fn locate(&self, day: u8, input: &str) -> ParseError {
+        let start = input.as_ptr() as usize;
+        let offset = self
+            .addr
+            .checked_sub(start)
+            .filter(|offset| offset + self.len <= input.len())
+            .unwrap_or(0);
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        let snippet = input[line_start..line_end].trim_end_matches('\r');
+        let span_end = (offset + self.len).min(line_end);
+        ParseError {
+            day,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            width: input[offset..span_end].chars().count().max(1),
+            snippet: snippet.to_string(),
+            message: self.message.clone(),
+        }
+    }
+}
+
+impl fmt::Display for SpanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for SpanError {}
+
+/// A parse failure located in a day's puzzle input. Lines and columns start
+/// at 1 and columns count characters, not bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    /// Number of characters, starting at `column`, that are at fault.
+    pub width: usize,
+    /// The whole line containing the error.
+    pub snippet: String,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Renders the message followed by the offending line with a caret
+    /// underline:
+    ///
+    /// ```text
+    /// Unknown color `purple`
+    ///  --> day 02, line 3, column 20
+    ///   |
+    /// 3 | Game 3: 8 green, 6 purple, 20 red
+    ///   |                    ^^^^^^
+    /// ```
+    pub fn render(&self) -> String {
+        let gutter = self.line.to_string().len();
+        let pad = " ".repeat(gutter);
+        format!(
+            "{message}\n\
+             {pad}--> day {day:02}, line {line}, column {column}\n\
+             {pad} |\n\
+             {line} | {snippet}\n\
+             {pad} | {indent}{carets}",
+            message = self.message,
+            day = self.day,
+            line = self.line,
+            column = self.column,
+            snippet = self.snippet,
+            indent = " ".repeat(self.column - 1),
+            carets = "^".repeat(self.width),
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {:02}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "Game 1: 3 blue\nGame 2: 8 purple, 6 blue\n";
+
+    #[test]
+    fn an_error_is_located_by_line_and_column() {
+        let purple = &INPUT[25..31];
+        let err = SpanError::at(purple, "Unknown color").locate(2, INPUT);
+        assert_eq!((err.line, err.column, err.width), (2, 11, 6));
+        assert_eq!(err.snippet, "Game 2: 8 purple, 6 blue");
+    }
+
+    #[test]
+    fn an_error_on_the_first_character_is_at_column_1() {
+        let err = SpanError::at(&INPUT[..4], "Bad").locate(2, INPUT);
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+
+    #[test]
+    fn an_empty_span_still_gets_one_caret() {
+        let end_of_line = &INPUT[14..14];
+        let err = SpanError::at(end_of_line, "Missing count").locate(2, INPUT);
+        assert_eq!((err.line, err.column, err.width), (1, 15, 1));
+    }
+
+    #[test]
+    fn a_span_from_another_string_falls_back_to_the_first_line() {
+        let other = String::from("elsewhere");
+        let err = SpanError::at(&other, "Bad").locate(2, INPUT);
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+
+    #[test]
+    fn a_rendered_error_underlines_the_span() {
+        let err = SpanError::at(&INPUT[25..31], "Unknown color `purple`").locate(2, INPUT);
+        assert_eq!(
+            err.render(),
+            "Unknown color `purple`\n \
+             --> day 02, line 2, column 11\n  \
+             |\n\
+             2 | Game 2: 8 purple, 6 blue\n  \
+             |           ^^^^^^"
+        );
+    }
+}
@@ -0,0 +1,239 @@
+//! `aoc repl`: parse an input once, then answer queries about the parsed data
+//! one line at a time. Besides the built-in commands, each day can offer its
+//! own queries through [`Solution::queries`].
+
+use std::{
+    io::{BufRead, Write},
+    str::FromStr,
+    time::Instant,
+};
+
+use crate::Solution;
+
+/// A command the REPL understands for one day.
+pub struct Query<P> {
+    pub name: &'static str,
+    /// How the arguments are written in `help`, such as `<row> <col>`.
+    pub args: &'static str,
+    pub help: &'static str,
+    pub run: fn(&P, &[&str]) -> Result<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Text(String),
+    Quit,
+}
+
+/// The `index`th argument, parsed; errors name the argument as `name`.
+pub fn arg<T>(args: &[&str], index: usize, name: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    let arg = args
+        .get(index)
+        .ok_or_else(|| format!("Missing argument <{name}>"))?;
+    arg.parse::<T>()
+        .map_err(|err| format!("Bad <{name}> `{arg}`: {err}"))
+}
+
+fn help<S: Solution>(queries: &[Query<S::Parsed>]) -> String {
+    let mut lines = vec![
+        ("help".to_string(), "Show this list"),
+        ("part <1|2>".to_string(), "Solve a part"),
+        ("quit".to_string(), "Leave (so does end of input)"),
+    ];
+    lines.extend(queries.iter().map(|q| {
+        (
+            format!("{} {}", q.name, q.args).trim_end().to_string(),
+            q.help,
+        )
+    }));
+    let width = lines
+        .iter()
+        .map(|(usage, _)| usage.len())
+        .max()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|(usage, help)| format!("{usage:<width$}  {help}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Answers one line typed at the REPL.
+pub fn respond<S: Solution>(
+    parsed: &S::Parsed,
+    queries: &[Query<S::Parsed>],
+    line: &str,
+) -> Result<Response, String> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    let Some((&command, args)) = words.split_first() else {
+        return Ok(Response::Text(String::new()));
+    };
+    match command {
+        "help" => Ok(Response::Text(help::<S>(queries))),
+        "quit" | "exit" => Ok(Response::Quit),
+        "part" => {
+            let solve_part = match arg::<u8>(args, 0, "part")? {
+                1 => S::part_one,
+                2 => S::part_two,
+                part => return Err(format!("There is no part {part}; parts are 1 and 2")),
+            };
+            let start = Instant::now();
+            let answer = solve_part(parsed)?;
+            Ok(Response::Text(format!("{answer} ({:?})", start.elapsed())))
+        }
+        _ => {
+            let query = queries
+                .iter()
+                .find(|q| q.name == command)
+                .ok_or_else(|| format!("Unknown command `{command}`; try `help`"))?;
+            (query.run)(parsed, args).map(Response::Text)
+        }
+    }
+}
+
+/// Parses `input` and answers each line of `commands` on `out` until `quit`
+/// or the end of the commands. With `prompt`, a prompt is shown before each
+/// line, for people typing at a terminal.
+pub fn repl<S: Solution>(
+    input: &str,
+    commands: &mut dyn BufRead,
+    out: &mut dyn Write,
+    prompt: bool,
+) -> Result<(), String> {
+    let write_err = |err: std::io::Error| format!("Could not write: {err}");
+    let parsed = S::parse(input).map_err(|err| err.locate(S::DAY, input).render())?;
+    let queries = S::queries();
+    if prompt {
+        writeln!(out, "Day {:02} parsed; `help` lists commands", S::DAY).map_err(write_err)?;
+    }
+    let mut line = String::new();
+    loop {
+        if prompt {
+            write!(out, "day-{:02}> ", S::DAY).map_err(write_err)?;
+            out.flush().map_err(write_err)?;
+        }
+        line.clear();
+        let read = commands
+            .read_line(&mut line)
+            .map_err(|err| format!("Could not read a command: {err}"))?;
+        if read == 0 {
+            return Ok(());
+        }
+        match respond::<S>(&parsed, &queries, &line) {
+            Ok(Response::Quit) => return Ok(()),
+            Ok(Response::Text(text)) if text.is_empty() => {}
+            Ok(Response::Text(text)) => writeln!(out, "{text}").map_err(write_err)?,
+            Err(msg) => writeln!(out, "ERROR {msg}").map_err(write_err)?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpanError;
+
+    struct Words;
+
+    impl Solution for Words {
+        const DAY: u8 = 99;
+
+        type Parsed = Vec<String>;
+
+        fn parse(input: &str) -> Result<Vec<String>, SpanError> {
+            Ok(input.split_whitespace().map(str::to_string).collect())
+        }
+
+        fn part_one(words: &Vec<String>) -> Result<String, String> {
+            Ok(words.len().to_string())
+        }
+
+        fn part_two(words: &Vec<String>) -> Result<String, String> {
+            Ok(words.concat())
+        }
+
+        fn queries() -> Vec<Query<Vec<String>>> {
+            vec![Query {
+                name: "word",
+                args: "<n>",
+                help: "Show the nth word",
+                run: |words, args| {
+                    let n = arg::<usize>(args, 0, "n")?;
+                    words
+                        .get(n.wrapping_sub(1))
+                        .cloned()
+                        .ok_or_else(|| format!("There is no word {n}"))
+                },
+            }]
+        }
+    }
+
+    fn session(input: &str, commands: &str) -> String {
+        let mut out = Vec::new();
+        repl::<Words>(input, &mut commands.as_bytes(), &mut out, false).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn queries_are_answered_line_by_line() {
+        assert_eq!(session("a b c", "word 2\n\nword 3\n"), "b\nc\n");
+    }
+
+    #[test]
+    fn quit_stops_before_the_remaining_lines() {
+        assert_eq!(session("a b", "word 1\nquit\nword 2\n"), "a\n");
+    }
+
+    #[test]
+    fn bad_commands_are_reported_and_the_session_goes_on() {
+        assert_eq!(
+            session("a", "frobnicate\nword\nword x\nword 5\nword 1\n"),
+            "ERROR Unknown command `frobnicate`; try `help`\n\
+             ERROR Missing argument <n>\n\
+             ERROR Bad <n> `x`: invalid digit found in string\n\
+             ERROR There is no word 5\n\
+             a\n"
+        );
+    }
+
+    #[test]
+    fn parts_can_be_solved_from_the_repl() {
+        let parsed = Words::parse("a b").unwrap();
+        let Ok(Response::Text(text)) = respond::<Words>(&parsed, &[], "part 2") else {
+            panic!("part 2 should be answered");
+        };
+        assert!(text.starts_with("ab ("));
+    }
+
+    #[test]
+    fn help_lists_built_ins_and_the_days_queries() {
+        let help = session("a", "help\n");
+        assert!(help.contains("part <1|2>  Solve a part"));
+        assert!(help.contains("word <n>    Show the nth word"));
+    }
+
+    #[test]
+    fn an_input_that_does_not_parse_is_an_error() {
+        struct Failing;
+        impl Solution for Failing {
+            const DAY: u8 = 98;
+            type Parsed = ();
+            fn parse(input: &str) -> Result<(), SpanError> {
+                Err(SpanError::at(input, "nope"))
+            }
+            fn part_one(_: &()) -> Result<String, String> {
+                Ok(String::new())
+            }
+            fn part_two(_: &()) -> Result<String, String> {
+                Ok(String::new())
+            }
+        }
+        let mut out = Vec::new();
+        let err = repl::<Failing>("x", &mut "".as_bytes(), &mut out, false).unwrap_err();
+        assert!(err.contains("nope"));
+    }
+}
@@ -0,0 +1,350 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc::{self, Allocs},
+    repl::Query,
+    ParseError, SpanError,
+};
+
+/// A day's puzzle, split so the input is parsed once and both parts are
+/// solved from the same parsed data.
+pub trait Solution {
+    const DAY: u8;
+
+    type Parsed;
+
+    fn parse(input: &str) -> Result<Self::Parsed, SpanError>;
+
+    /// The answer to part 1, or why an input that parsed has none.
+    fn part_one(parsed: &Self::Parsed) -> Result<String, String>;
+
+    /// The answer to part 2, or why an input that parsed has none.
+    fn part_two(parsed: &Self::Parsed) -> Result<String, String>;
+
+    /// Other implementations of the parts, which `crosscheck` runs next to
+    /// `part_one` and `part_two`.
+    fn alternates() -> Vec<Alternate<Self::Parsed>> {
+        Vec::new()
+    }
+
+    /// Commands `aoc repl` offers for this day's parsed input, besides the
+    /// built-in ones.
+    fn queries() -> Vec<Query<Self::Parsed>> {
+        Vec::new()
+    }
+}
+
+/// Another way to solve a part, usually slower but more obviously correct.
+pub struct Alternate<P> {
+    pub name: &'static str,
+    pub part: u8,
+    /// The answer, or `None` when the input is too big to solve this way.
+    pub solve: fn(&P) -> Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartAnswer {
+    pub part: u8,
+    pub answer: String,
+    pub elapsed: Duration,
+    pub allocs: Allocs,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub day: u8,
+    pub parse_time: Duration,
+    pub parse_allocs: Allocs,
+    pub parts: Vec<PartAnswer>,
+}
+
+/// Why an input could not be solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// The input does not parse.
+    Parse(ParseError),
+    /// A part has no answer for an input that parsed, such as day 8 part 1
+    /// on a map without `AAA`.
+    Part { day: u8, part: u8, message: String },
+}
+
+impl SolveError {
+    /// A parse error with its caret diagram, or a part failure on one line.
+    pub fn render(&self) -> String {
+        match self {
+            SolveError::Parse(err) => err.render(),
+            SolveError::Part { .. } => self.to_string(),
+        }
+    }
+}
+
+impl From<ParseError> for SolveError {
+    fn from(err: ParseError) -> SolveError {
+        SolveError::Parse(err)
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Parse(err) => err.fmt(f),
+            SolveError::Part { day, part, message } => {
+                write!(f, "day {day:02}, part {part}: {message}")
+            }
+        }
+    }
+}
+
+/// One implementation's answer in a cross-check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Implementation {
+    pub name: &'static str,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+}
+
+/// Every implementation's answer to one part, the main one first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrossCheck {
+    pub day: u8,
+    pub part: u8,
+    pub implementations: Vec<Implementation>,
+}
+
+impl CrossCheck {
+    /// Whether every implementation that gave an answer gave the main one.
+    pub fn agrees(&self) -> bool {
+        let main = &self.implementations[0].answer;
+        self.implementations
+            .iter()
+            .all(|i| i.answer.is_none() || &i.answer == main)
+    }
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
+
+/// Times `f` and, when counting is on, counts what it allocates.
+fn measured<T>(f: impl FnOnce() -> T) -> (T, Duration, Allocs) {
+    let ((value, elapsed), allocs) = alloc::measure(|| timed(f));
+    (value, elapsed, allocs)
+}
+
+/// Parses `input` once and solves each of the requested `parts` from the
+/// parsed data, timing every step. Under a [`budget`](crate::budget), each
+/// part gets the whole budget; while [`alloc`] counting is on, each step's
+/// allocations are counted too. The first part without an answer stops the
+/// rest.
+///
+/// # Panics
+///
+/// If a part other than 1 or 2 is requested.
+pub fn solve<S: Solution>(input: &str, parts: &[u8]) -> Result<Answers, SolveError> {
+    let (parsed, parse_time, parse_allocs) = measured(|| S::parse(input));
+    let parsed = parsed.map_err(|err| err.locate(S::DAY, input))?;
+    let parts = parts
+        .iter()
+        .map(|&part| {
+            let solve_part = match part {
+                1 => S::part_one,
+                2 => S::part_two,
+                _ => panic!("There is no part {part}; parts are 1 and 2"),
+            };
+            crate::budget::start_part(part);
+            let (answer, elapsed, allocs) = measured(|| solve_part(&parsed));
+            Ok(PartAnswer {
+                part,
+                answer: answer.map_err(|message| part_error::<S>(part, message))?,
+                elapsed,
+                allocs,
+            })
+        })
+        .collect::<Result<_, SolveError>>()?;
+    Ok(Answers {
+        day: S::DAY,
+        parse_time,
+        parse_allocs,
+        parts,
+    })
+}
+
+/// Parses `input` once and solves each of the requested `parts` with the
+/// main implementation and then every alternate for that part.
+///
+/// # Panics
+///
+/// If a part other than 1 or 2 is requested.
+pub fn crosscheck<S: Solution>(input: &str, parts: &[u8]) -> Result<Vec<CrossCheck>, SolveError> {
+    let parsed = S::parse(input).map_err(|err| err.locate(S::DAY, input))?;
+    let alternates = S::alternates();
+    let checks = parts
+        .iter()
+        .map(|&part| {
+            let main = match part {
+                1 => S::part_one,
+                2 => S::part_two,
+                _ => panic!("There is no part {part}; parts are 1 and 2"),
+            };
+            let (answer, elapsed) = timed(|| main(&parsed));
+            let answer = answer.map_err(|message| part_error::<S>(part, message))?;
+            let mut implementations = vec![Implementation {
+                name: "main",
+                answer: Some(answer),
+                elapsed,
+            }];
+            for alternate in alternates.iter().filter(|a| a.part == part) {
+                let (answer, elapsed) = timed(|| (alternate.solve)(&parsed));
+                implementations.push(Implementation {
+                    name: alternate.name,
+                    answer,
+                    elapsed,
+                });
+            }
+            Ok(CrossCheck {
+                day: S::DAY,
+                part,
+                implementations,
+            })
+        })
+        .collect::<Result<_, SolveError>>()?;
+    Ok(checks)
+}
+
+fn part_error<S: Solution>(part: u8, message: String) -> SolveError {
+    SolveError::Part {
+        day: S::DAY,
+        part,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 99;
+
+        type Parsed = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Vec<u32>, SpanError> {
+            input
+                .split_whitespace()
+                .map(|n| {
+                    n.parse::<u32>()
+                        .map_err(|err| SpanError::at(n, err.to_string()))
+                })
+                .collect()
+        }
+
+        fn part_one(parsed: &Vec<u32>) -> Result<String, String> {
+            Ok(parsed.iter().sum::<u32>().to_string())
+        }
+
+        fn part_two(parsed: &Vec<u32>) -> Result<String, String> {
+            parsed
+                .iter()
+                .try_fold(1_u32, |product, &n| product.checked_mul(n))
+                .map(|product| product.to_string())
+                .ok_or_else(|| "The product is too large".to_string())
+        }
+
+        fn alternates() -> Vec<Alternate<Vec<u32>>> {
+            vec![
+                Alternate {
+                    name: "reversed",
+                    part: 1,
+                    solve: |parsed| Some(parsed.iter().rev().sum::<u32>().to_string()),
+                },
+                Alternate {
+                    name: "off_by_one",
+                    part: 2,
+                    solve: |parsed| Some((parsed.iter().product::<u32>() + 1).to_string()),
+                },
+                Alternate {
+                    name: "too_slow",
+                    part: 2,
+                    solve: |_| None,
+                },
+            ]
+        }
+    }
+
+    fn answers(input: &str, parts: &[u8]) -> Vec<String> {
+        solve::<Sum>(input, parts)
+            .unwrap()
+            .parts
+            .into_iter()
+            .map(|p| p.answer)
+            .collect()
+    }
+
+    #[test]
+    fn both_parts_are_solved_from_one_parse() {
+        assert_eq!(answers("2 3 4", &[1, 2]), ["9", "24"]);
+    }
+
+    #[test]
+    fn only_the_requested_parts_are_solved() {
+        assert_eq!(answers("2 3 4", &[2]), ["24"]);
+    }
+
+    #[test]
+    fn answers_carry_the_day() {
+        assert_eq!(solve::<Sum>("1", &[1]).unwrap().day, 99);
+    }
+
+    #[test]
+    fn parse_errors_are_located_in_the_input() {
+        let Err(SolveError::Parse(err)) = solve::<Sum>("1 2\n3 x", &[1]) else {
+            panic!("`x` should not parse");
+        };
+        assert_eq!((err.day, err.line, err.column), (99, 2, 3));
+    }
+
+    #[test]
+    fn a_part_without_an_answer_is_an_error() {
+        let input = "65536 65536";
+        assert_eq!(answers(input, &[1]), ["131072"]);
+        let err = solve::<Sum>(input, &[1, 2]).unwrap_err();
+        assert_eq!(err.render(), "day 99, part 2: The product is too large");
+        assert!(crosscheck::<Sum>(input, &[2]).is_err());
+    }
+
+    #[test]
+    fn crosscheck_runs_the_main_implementation_then_the_alternates() {
+        let checks = crosscheck::<Sum>("2 3 4", &[1, 2]).unwrap();
+        let names = |check: &CrossCheck| {
+            check
+                .implementations
+                .iter()
+                .map(|i| i.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&checks[0]), ["main", "reversed"]);
+        assert_eq!(names(&checks[1]), ["main", "off_by_one", "too_slow"]);
+    }
+
+    #[test]
+    fn crosscheck_finds_disagreements_but_ignores_missing_answers() {
+        let checks = crosscheck::<Sum>("2 3 4", &[1, 2]).unwrap();
+        assert!(checks[0].agrees());
+        assert!(!checks[1].agrees());
+        assert_eq!(checks[1].implementations[1].answer.as_deref(), Some("25"));
+        assert_eq!(checks[1].implementations[2].answer, None);
+    }
+
+    #[test]
+    #[should_panic(expected = "There is no part 3")]
+    fn unknown_parts_panic() {
+        let _ = solve::<Sum>("1", &[3]);
+    }
+}
@@ -0,0 +1,250 @@
+//! Intermediate steps of a solution, written to stderr when a runner is given
+//! `--trace text|json`. Days emit events with [`trace!`](crate::trace!), which
+//! costs a single check when tracing is off.
+//!
+//! Tracing is switched on per thread, for the thread that solves the input.
+
+use std::{cell::RefCell, fmt::Write, str::FromStr};
+
+use crate::json_escape;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// `[day-08] visit step=3 node=CCC`
+    Text,
+    /// `{"source":"day-08","event":"visit","step":3,"node":"CCC"}`
+    Json,
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<TraceFormat, <TraceFormat as FromStr>::Err> {
+        match s {
+            "text" => Ok(TraceFormat::Text),
+            "json" => Ok(TraceFormat::Json),
+            other => Err(format!("Unknown trace format `{other}`; use text or json")),
+        }
+    }
+}
+
+struct Tracer {
+    format: TraceFormat,
+    /// Where captured lines go; `None` writes them to stderr.
+    captured: Option<Vec<String>>,
+}
+
+thread_local! {
+    static TRACER: RefCell<Option<Tracer>> = const { RefCell::new(None) };
+}
+
+/// Writes every event emitted on this thread from now on to stderr.
+pub fn enable(format: TraceFormat) {
+    TRACER.with(|tracer| {
+        *tracer.borrow_mut() = Some(Tracer {
+            format,
+            captured: None,
+        })
+    });
+}
+
+pub fn enabled() -> bool {
+    TRACER.with(|tracer| tracer.borrow().is_some())
+}
+
+/// Runs `f` with tracing on and returns the lines it emitted instead of
+/// writing them out.
+pub fn capture<R>(format: TraceFormat, f: impl FnOnce() -> R) -> (R, Vec<String>) {
+    let previous = TRACER.with(|tracer| {
+        tracer.borrow_mut().replace(Tracer {
+            format,
+            captured: Some(Vec::new()),
+        })
+    });
+    let result = f();
+    let tracer = TRACER.with(|tracer| tracer.replace(previous));
+    let lines = tracer.and_then(|t| t.captured).unwrap_or_default();
+    (result, lines)
+}
+
+/// A value that can be a field of a trace event.
+pub trait Traced {
+    fn text(&self) -> String;
+
+    fn json(&self) -> String;
+}
+
+macro_rules! traced_number {
+    ($($t:ty),*) => {
+        $(impl Traced for $t {
+            fn text(&self) -> String {
+                self.to_string()
+            }
+
+            fn json(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+traced_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, bool);
+
+impl Traced for str {
+    fn text(&self) -> String {
+        self.to_string()
+    }
+
+    fn json(&self) -> String {
+        format!("\"{}\"", json_escape(self))
+    }
+}
+
+impl Traced for String {
+    fn text(&self) -> String {
+        self.as_str().text()
+    }
+
+    fn json(&self) -> String {
+        self.as_str().json()
+    }
+}
+
+impl<T: Traced> Traced for [T] {
+    fn text(&self) -> String {
+        let items = self.iter().map(T::text).collect::<Vec<_>>();
+        format!("[{}]", items.join(", "))
+    }
+
+    fn json(&self) -> String {
+        let items = self.iter().map(T::json).collect::<Vec<_>>();
+        format!("[{}]", items.join(","))
+    }
+}
+
+impl<T: Traced> Traced for Vec<T> {
+    fn text(&self) -> String {
+        self.as_slice().text()
+    }
+
+    fn json(&self) -> String {
+        self.as_slice().json()
+    }
+}
+
+impl<T: Traced + ?Sized> Traced for &T {
+    fn text(&self) -> String {
+        (**self).text()
+    }
+
+    fn json(&self) -> String {
+        (**self).json()
+    }
+}
+
+/// Formats one event and hands it to this thread's tracer, if any. Called by
+/// [`trace!`](crate::trace!) with the emitting package's name as `source`.
+pub fn emit(source: &str, event: &str, fields: &[(&str, &dyn Traced)]) {
+    TRACER.with(|tracer| {
+        let mut tracer = tracer.borrow_mut();
+        let Some(tracer) = tracer.as_mut() else {
+            return;
+        };
+        let mut line = String::new();
+        match tracer.format {
+            TraceFormat::Text => {
+                write!(line, "[{source}] {event}").expect("Writing to a String cannot fail");
+                for (name, value) in fields {
+                    write!(line, " {name}={}", value.text())
+                        .expect("Writing to a String cannot fail");
+                }
+            }
+            TraceFormat::Json => {
+                write!(
+                    line,
+                    "{{\"source\":\"{}\",\"event\":\"{}\"",
+                    json_escape(source),
+                    json_escape(event)
+                )
+                .expect("Writing to a String cannot fail");
+                for (name, value) in fields {
+                    write!(line, ",\"{}\":{}", json_escape(name), value.json())
+                        .expect("Writing to a String cannot fail");
+                }
+                line.push('}');
+            }
+        }
+        match &mut tracer.captured {
+            Some(lines) => lines.push(line),
+            None => eprintln!("{line}"),
+        }
+    });
+}
+
+/// Emits a trace event named `$event` with `name = value` fields, when tracing
+/// is on:
+///
+/// ```
+/// let (node, step) = ("CCC", 3);
+/// aoc_common::trace!("visit", step = step, node = node);
+/// ```
+#[macro_export]
+macro_rules! trace {
+    ($event:literal $(, $name:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::enabled() {
+            $crate::trace::emit(
+                env!("CARGO_PKG_NAME"),
+                $event,
+                &[$((stringify!($name), &$value as &dyn $crate::trace::Traced)),*],
+            );
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_are_only_emitted_while_tracing() {
+        crate::trace!("ignored", value = 1);
+        let ((), lines) = capture(TraceFormat::Text, || {
+            crate::trace!("kept", value = 2);
+        });
+        assert_eq!(lines, ["[aoc-common] kept value=2"]);
+        assert!(!enabled());
+    }
+
+    #[test]
+    fn text_events_list_their_fields() {
+        let row = vec![3, 3, 3];
+        let ((), lines) = capture(TraceFormat::Text, || {
+            crate::trace!("row", depth = 1_usize, row = row, node = "CCC");
+        });
+        assert_eq!(lines, ["[aoc-common] row depth=1 row=[3, 3, 3] node=CCC"]);
+    }
+
+    #[test]
+    fn json_events_are_one_object_per_line() {
+        let ((), lines) = capture(TraceFormat::Json, || {
+            crate::trace!(
+                "convert",
+                from = "seed",
+                value = 79_u32,
+                ranges = vec![1, -2]
+            );
+        });
+        assert_eq!(
+            lines,
+            [
+                r#"{"source":"aoc-common","event":"convert","from":"seed","value":79,"ranges":[1,-2]}"#
+            ]
+        );
+    }
+
+    #[test]
+    fn trace_formats_are_parsed_by_name() {
+        assert_eq!("json".parse::<TraceFormat>(), Ok(TraceFormat::Json));
+        assert!("tsv".parse::<TraceFormat>().is_err());
+    }
+}
@@ -0,0 +1,90 @@
+//! Every implemented day, in order, for the tools that run days by number:
+//! `aoc` and the bindings.
+
+use std::io::{BufRead, Write};
+
+use aoc_common::{Answers, CrossCheck, Solution, SolveError};
+
+/// `aoc_common::repl::repl` for one day.
+type Repl = fn(&str, &mut dyn BufRead, &mut dyn Write, bool) -> Result<(), String>;
+
+pub struct Day {
+    pub number: u8,
+    /// Parses an input once and solves the listed parts from it.
+    pub solve: fn(&str, &[u8]) -> Result<Answers, SolveError>,
+    /// Solves the listed parts with every implementation the day has.
+    pub crosscheck: fn(&str, &[u8]) -> Result<Vec<CrossCheck>, SolveError>,
+    /// Parses an input once and answers commands about it until they run out.
+    pub repl: Repl,
+}
+
+macro_rules! day {
+    ($solution:ty) => {
+        Day {
+            number: <$solution as Solution>::DAY,
+            solve: aoc_common::solve::<$solution>,
+            crosscheck: aoc_common::crosscheck::<$solution>,
+            repl: aoc_common::repl::repl::<$solution>,
+        }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(day_01::Day01),
+    day!(day_02::Day02),
+    day!(day_03::Day03),
+    day!(day_04::Day04),
+    day!(day_05::Day05),
+    day!(day_07::Day07),
+    day!(day_08::Day08),
+    day!(day_09::Day09),
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_are_listed_in_order_without_duplicates() {
+        assert!(DAYS.windows(2).all(|w| w[0].number < w[1].number));
+    }
+
+    #[test]
+    fn a_missing_day_cannot_be_found() {
+        assert!(find(6).is_none());
+    }
+
+    #[test]
+    fn a_day_dispatches_to_its_parts() {
+        let day = find(9).unwrap();
+        let answers = (day.solve)("10 13 16 21 30 45", &[2]).unwrap();
+        assert_eq!(answers.day, 9);
+        assert_eq!(answers.parts[0].answer, "5");
+    }
+
+    #[test]
+    fn a_day_dispatches_to_its_alternates() {
+        let day = find(7).unwrap();
+        let checks = (day.crosscheck)("T55J5 684\nKTJJT 220", &[2]).unwrap();
+        assert_eq!(checks[0].implementations.len(), 2);
+        assert!(checks[0].agrees());
+    }
+
+    #[test]
+    fn a_day_dispatches_to_its_queries() {
+        let day = find(4).unwrap();
+        let mut out = Vec::new();
+        (day.repl)(
+            "Card 1: 1 2 | 2 3",
+            &mut "card 1\n".as_bytes(),
+            &mut out,
+            false,
+        )
+        .unwrap();
+        assert!(String::from_utf8(out).unwrap().contains("1 match"));
+    }
+}
@@ -0,0 +1,8 @@
+#!/bin/sh
+# Regenerates include/aoc_ffi.h from the exported functions. Commit the
+# result with the change to them; `cargo test` fails while it is stale.
+# Usage: aoc-ffi/gen-header.sh
+set -eu
+
+cd "$(dirname "$0")/.."
+AOC_FFI_WRITE_HEADER=1 cargo test -q -p aoc-ffi --test header
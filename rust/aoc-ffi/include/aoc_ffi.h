@@ -0,0 +1,92 @@
+#ifndef AOC_FFI_H
+#define AOC_FFI_H
+
+/* Generated from src/lib.rs by aoc-ffi/gen-header.sh; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * What became of a call. Every status but `AOC_STATUS_OK` and
+ * `AOC_STATUS_BUFFER_TOO_SMALL` comes with a message in the output buffer.
+ */
+typedef enum {
+  AOC_STATUS_OK = 0,
+  /**
+   * There is no solution for the day; `aoc_days` lists those there are.
+   */
+  AOC_STATUS_UNKNOWN_DAY = 1,
+  /**
+   * Parts are 1 and 2.
+   */
+  AOC_STATUS_UNKNOWN_PART = 2,
+  /**
+   * A pointer was null where a value is needed.
+   */
+  AOC_STATUS_NULL_POINTER = 3,
+  /**
+   * The input is not UTF-8.
+   */
+  AOC_STATUS_INVALID_UTF8 = 4,
+  /**
+   * The input does not parse; the message says where and why.
+   */
+  AOC_STATUS_PARSE_ERROR = 5,
+  /**
+   * The output buffer cannot hold the answer. The length it needs, less
+   * the terminating NUL, is in `*out_len`.
+   */
+  AOC_STATUS_BUFFER_TOO_SMALL = 6,
+  /**
+   * The solver failed on an input that parsed.
+   */
+  AOC_STATUS_PANIC = 7,
+  /**
+   * The input parsed but the part has no answer for it; the message says
+   * why.
+   */
+  AOC_STATUS_NO_ANSWER = 8,
+} AocStatus;
+
+/**
+ * Solves `part` of `day` for the `input_len` bytes of UTF-8 at `input`.
+ *
+ * `*out_len` is the size of `out` on the way in. On `AOC_STATUS_OK` the
+ * answer is in `out`, NUL-terminated, and `*out_len` is its length without
+ * the NUL. If `out` is too small, `*out_len` becomes the length needed and
+ * the status is `AOC_STATUS_BUFFER_TOO_SMALL`; a null `out` asks for just
+ * that. On other failures `out` holds as much of the error message as fits.
+ *
+ * # Safety
+ *
+ * `input` must point to `input_len` readable bytes (or may be null if
+ * `input_len` is 0), `out_len` must be valid to read and write, and `out`
+ * must be null or writable for `*out_len` bytes.
+ */
+AocStatus aoc_solve(uint8_t day,
+                    uint8_t part,
+                    const uint8_t *input,
+                    size_t input_len,
+                    char *out,
+                    size_t *out_len);
+
+/**
+ * Writes the implemented days, in order, into `out` (up to `out_len` of
+ * them) and returns how many there are in all.
+ *
+ * # Safety
+ *
+ * `out` must be null or writable for `out_len` bytes.
+ */
+size_t aoc_days(uint8_t *out, size_t out_len);
+
+/**
+ * A short description of `status`, such as `"parse error"`. The string is
+ * static; don't free it.
+ */
+const char *aoc_status_name(int32_t status);
+
+#endif  /* AOC_FFI_H */
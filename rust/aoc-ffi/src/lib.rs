@@ -0,0 +1,300 @@
+//! A C ABI for the day solvers, built as `libaoc_ffi` with the header
+//! `include/aoc_ffi.h`:
+//!
+//! ```c
+//! char answer[64];
+//! size_t len = sizeof answer;
+//! AocStatus status = aoc_solve(7, 2, input, input_len, answer, &len);
+//! if (status != AOC_STATUS_OK) {
+//!     fprintf(stderr, "%s: %s\n", aoc_status_name(status), answer);
+//! }
+//! ```
+//!
+//! Every function is safe to call from several threads at once, and no
+//! panic crosses into C.
+
+use std::{
+    ffi::c_char,
+    panic::{self, AssertUnwindSafe},
+    slice,
+};
+
+use aoc_common::SolveError;
+
+/// What became of a call. Every status but `AOC_STATUS_OK` and
+/// `AOC_STATUS_BUFFER_TOO_SMALL` comes with a message in the output buffer.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    /// There is no solution for the day; `aoc_days` lists those there are.
+    UnknownDay = 1,
+    /// Parts are 1 and 2.
+    UnknownPart = 2,
+    /// A pointer was null where a value is needed.
+    NullPointer = 3,
+    /// The input is not UTF-8.
+    InvalidUtf8 = 4,
+    /// The input does not parse; the message says where and why.
+    ParseError = 5,
+    /// The output buffer cannot hold the answer. The length it needs, less
+    /// the terminating NUL, is in `*out_len`.
+    BufferTooSmall = 6,
+    /// The solver failed on an input that parsed.
+    Panic = 7,
+    /// The input parsed but the part has no answer for it; the message says
+    /// why.
+    NoAnswer = 8,
+}
+
+/// A failed call: its status and the message to hand back with it.
+type Failure = (AocStatus, String);
+
+/// The answer to `part` of `day` for `input`.
+fn solve(day: u8, part: u8, input: &[u8]) -> Result<String, Failure> {
+    let solver = aoc_days::find(day).ok_or_else(|| {
+        (
+            AocStatus::UnknownDay,
+            format!("Day {day} is not implemented"),
+        )
+    })?;
+    if !(1..=2).contains(&part) {
+        return Err((
+            AocStatus::UnknownPart,
+            format!("There is no part {part}; parts are 1 and 2"),
+        ));
+    }
+    let input = std::str::from_utf8(input)
+        .map_err(|err| (AocStatus::InvalidUtf8, format!("Input is not UTF-8: {err}")))?;
+    let solved = panic::catch_unwind(AssertUnwindSafe(|| (solver.solve)(input, &[part]))).map_err(
+        |panic| {
+            let reason = panic
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("no reason given");
+            (
+                AocStatus::Panic,
+                format!("Day {day} part {part} failed: {reason}"),
+            )
+        },
+    )?;
+    let mut answers = solved.map_err(|err| match err {
+        SolveError::Parse(_) => (AocStatus::ParseError, err.to_string()),
+        SolveError::Part { .. } => (AocStatus::NoAnswer, err.to_string()),
+    })?;
+    Ok(answers.parts.swap_remove(0).answer)
+}
+
+/// Copies `text` into `out`, NUL-terminated, and sets `*out_len` to its
+/// length. Answers must fit whole; error messages are cut short to fit.
+///
+/// # Safety
+///
+/// `out_len` must be valid, and `out` valid for `*out_len` bytes unless null.
+unsafe fn write_out(text: &str, truncate: bool, out: *mut c_char, out_len: *mut usize) -> bool {
+    let capacity = if out.is_null() { 0 } else { *out_len };
+    let len = if text.len() < capacity {
+        text.len()
+    } else if truncate && capacity > 0 {
+        // Never split a character
+        (0..capacity)
+            .rev()
+            .find(|&i| text.is_char_boundary(i))
+            .unwrap_or(0)
+    } else {
+        *out_len = text.len();
+        if capacity > 0 {
+            *out = 0;
+        }
+        return false;
+    };
+    std::ptr::copy_nonoverlapping(text.as_ptr(), out.cast::<u8>(), len);
+    *out.add(len) = 0;
+    *out_len = len;
+    true
+}
+
+/// Solves `part` of `day` for the `input_len` bytes of UTF-8 at `input`.
+///
+/// `*out_len` is the size of `out` on the way in. On `AOC_STATUS_OK` the
+/// answer is in `out`, NUL-terminated, and `*out_len` is its length without
+/// the NUL. If `out` is too small, `*out_len` becomes the length needed and
+/// the status is `AOC_STATUS_BUFFER_TOO_SMALL`; a null `out` asks for just
+/// that. On other failures `out` holds as much of the error message as fits.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes (or may be null if
+/// `input_len` is 0), `out_len` must be valid to read and write, and `out`
+/// must be null or writable for `*out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    out: *mut c_char,
+    out_len: *mut usize,
+) -> AocStatus {
+    if out_len.is_null() {
+        return AocStatus::NullPointer;
+    }
+    let input = match (input.is_null(), input_len) {
+        (true, 0) => &[][..],
+        (true, _) => {
+            let msg = "Input is null but its length is not 0";
+            write_out(msg, true, out, out_len);
+            return AocStatus::NullPointer;
+        }
+        (false, _) => slice::from_raw_parts(input, input_len),
+    };
+    match solve(day, part, input) {
+        Ok(answer) if write_out(&answer, false, out, out_len) => AocStatus::Ok,
+        Ok(_) => AocStatus::BufferTooSmall,
+        Err((status, msg)) => {
+            write_out(&msg, true, out, out_len);
+            status
+        }
+    }
+}
+
+/// Writes the implemented days, in order, into `out` (up to `out_len` of
+/// them) and returns how many there are in all.
+///
+/// # Safety
+///
+/// `out` must be null or writable for `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_days(out: *mut u8, out_len: usize) -> usize {
+    let days = aoc_days::DAYS;
+    if !out.is_null() {
+        for (slot, day) in slice::from_raw_parts_mut(out, out_len).iter_mut().zip(days) {
+            *slot = day.number;
+        }
+    }
+    days.len()
+}
+
+/// A short description of `status`, such as `"parse error"`. The string is
+/// static; don't free it.
+#[no_mangle]
+pub extern "C" fn aoc_status_name(status: i32) -> *const c_char {
+    // Taken as a plain integer, as C may hand over any value
+    let name = match status {
+        0 => c"ok",
+        1 => c"unknown day",
+        2 => c"unknown part",
+        3 => c"null pointer",
+        4 => c"input is not UTF-8",
+        5 => c"parse error",
+        6 => c"buffer too small",
+        7 => c"solver failed",
+        8 => c"no answer",
+        _ => c"unknown status",
+    };
+    name.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CStr;
+
+    use super::*;
+
+    const HANDS: &[u8] = b"32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n";
+
+    /// Calls `aoc_solve` with a buffer of `capacity` bytes, returning the
+    /// status, the text written and `*out_len`.
+    fn call(day: u8, part: u8, input: &[u8], capacity: usize) -> (AocStatus, String, usize) {
+        let mut out = vec![b'#'; capacity];
+        let mut len = capacity;
+        let status = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr().cast(),
+                &mut len,
+            )
+        };
+        let text = out.iter().take_while(|&&b| b != 0 && b != b'#');
+        let text = String::from_utf8(text.copied().collect()).unwrap();
+        (status, text, len)
+    }
+
+    #[test]
+    fn answers_are_written_nul_terminated() {
+        assert_eq!(
+            call(7, 1, HANDS, 16),
+            (AocStatus::Ok, "6440".to_string(), 4)
+        );
+        assert_eq!(call(7, 2, HANDS, 5), (AocStatus::Ok, "5905".to_string(), 4));
+    }
+
+    #[test]
+    fn a_small_buffer_reports_the_length_needed() {
+        assert_eq!(
+            call(7, 1, HANDS, 4),
+            (AocStatus::BufferTooSmall, String::new(), 4)
+        );
+        let mut len = 0;
+        let status = unsafe {
+            aoc_solve(
+                7,
+                1,
+                HANDS.as_ptr(),
+                HANDS.len(),
+                std::ptr::null_mut(),
+                &mut len,
+            )
+        };
+        assert_eq!((status, len), (AocStatus::BufferTooSmall, 4));
+    }
+
+    #[test]
+    fn parse_errors_say_where_the_input_goes_wrong() {
+        let (status, msg, _) = call(7, 1, b"32T3K 765\nKK677 lots\n", 256);
+        assert_eq!(status, AocStatus::ParseError);
+        assert!(msg.starts_with("day 07, line 2, column 7: "), "{msg}");
+    }
+
+    #[test]
+    fn a_part_without_an_answer_says_why() {
+        let (status, msg, _) = call(8, 1, b"L\n\nAAA = (AAA, AAA)\n", 256);
+        assert_eq!(status, AocStatus::NoAnswer);
+        assert_eq!(msg, "day 08, part 1: The map has no node `ZZZ`");
+    }
+
+    #[test]
+    fn error_messages_are_cut_short_to_fit() {
+        let (status, msg, len) = call(6, 1, b"", 8);
+        assert_eq!(status, AocStatus::UnknownDay);
+        assert_eq!((msg.as_str(), len), ("Day 6 i", 7));
+    }
+
+    #[test]
+    fn bad_arguments_are_reported() {
+        assert_eq!(call(7, 3, HANDS, 64).0, AocStatus::UnknownPart);
+        assert_eq!(call(7, 1, b"\xff 765\n", 64).0, AocStatus::InvalidUtf8);
+        let status = unsafe { aoc_solve(7, 1, std::ptr::null(), 3, std::ptr::null_mut(), &mut 0) };
+        assert_eq!(status, AocStatus::NullPointer);
+    }
+
+    #[test]
+    fn every_day_is_listed() {
+        let mut days = [0; 32];
+        let count = unsafe { aoc_days(days.as_mut_ptr(), days.len()) };
+        assert_eq!(&days[..count], [1, 2, 3, 4, 5, 7, 8, 9]);
+        assert_eq!(unsafe { aoc_days(std::ptr::null_mut(), 0) }, count);
+    }
+
+    #[test]
+    fn every_status_has_a_name() {
+        let name = |status| unsafe { CStr::from_ptr(aoc_status_name(status)) };
+        assert_eq!(name(AocStatus::ParseError as i32), c"parse error");
+        assert_eq!(name(AocStatus::Panic as i32), c"solver failed");
+        assert_eq!(name(-1), c"unknown status");
+    }
+}
@@ -0,0 +1,13 @@
+#!/bin/sh
+# Builds libaoc_ffi, then compiles the C test program against it and its
+# header and runs it.
+# Usage: aoc-ffi/test.sh
+set -eu
+
+cd "$(dirname "$0")/.."
+cargo build -q -p aoc-ffi
+mkdir -p target/c
+${CC:-cc} -std=c11 -Wall -Wextra -Werror -Iaoc-ffi/include \
+    -o target/c/test_aoc_ffi aoc-ffi/tests/test_aoc_ffi.c \
+    -Ltarget/debug -laoc_ffi
+LD_LIBRARY_PATH=target/debug exec target/c/test_aoc_ffi
@@ -0,0 +1,26 @@
+//! Keeps the checked-in `include/aoc_ffi.h` in step with the exported
+//! functions. `aoc-ffi/gen-header.sh` runs this with `AOC_FFI_WRITE_HEADER`
+//! set to rewrite the header instead.
+
+use std::{env, fs, path::Path};
+
+#[test]
+fn the_header_matches_the_exported_functions() {
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let config = cbindgen::Config::from_file(Path::new(crate_dir).join("cbindgen.toml"))
+        .expect("cbindgen.toml should be valid");
+    let mut header = Vec::new();
+    cbindgen::generate_with_config(crate_dir, config)
+        .expect("the exported functions should be expressible in C")
+        .write(&mut header);
+    let path = Path::new(crate_dir).join("include/aoc_ffi.h");
+    if env::var_os("AOC_FFI_WRITE_HEADER").is_some() {
+        fs::write(&path, header).expect("include/aoc_ffi.h should be writable");
+    } else {
+        let checked_in = fs::read(&path).unwrap_or_default();
+        assert!(
+            checked_in == header,
+            "include/aoc_ffi.h is out of date; run aoc-ffi/gen-header.sh"
+        );
+    }
+}
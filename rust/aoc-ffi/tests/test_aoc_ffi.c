@@ -0,0 +1,133 @@
+/* Tests for libaoc_ffi through its C header; run them with aoc-ffi/test.sh,
+ * which runs this from rust/ so the day examples can be read. */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "aoc_ffi.h"
+
+static int failures = 0;
+
+#define CHECK(cond, ...)                                              \
+    do {                                                              \
+        if (!(cond)) {                                                \
+            fprintf(stderr, "%s:%d: FAILED %s: ", __FILE__, __LINE__, \
+                    #cond);                                           \
+            fprintf(stderr, __VA_ARGS__);                             \
+            fprintf(stderr, "\n");                                    \
+            failures++;                                               \
+        }                                                             \
+    } while (0)
+
+/* The whole of `path`, NUL-terminated; its length goes in `*len`. */
+static char *read_file(const char *path, size_t *len) {
+    FILE *file = fopen(path, "rb");
+    if (file == NULL) {
+        perror(path);
+        exit(2);
+    }
+    fseek(file, 0, SEEK_END);
+    *len = (size_t)ftell(file);
+    rewind(file);
+    char *text = malloc(*len + 1);
+    if (text == NULL || fread(text, 1, *len, file) != *len) {
+        perror(path);
+        exit(2);
+    }
+    text[*len] = '\0';
+    fclose(file);
+    return text;
+}
+
+static void check_answer(unsigned day, unsigned part, const char *path, const char *expected) {
+    size_t input_len;
+    char *input = read_file(path, &input_len);
+    char answer[64];
+    size_t len = sizeof answer;
+    AocStatus status = aoc_solve(day, part, (const uint8_t *)input, input_len, answer, &len);
+    CHECK(status == AOC_STATUS_OK, "day %u part %u: %s: %s", day, part,
+          aoc_status_name(status), answer);
+    CHECK(status != AOC_STATUS_OK || strcmp(answer, expected) == 0,
+          "day %u part %u gave %s, not %s", day, part, answer, expected);
+    CHECK(status != AOC_STATUS_OK || len == strlen(expected), "length %zu", len);
+    free(input);
+}
+
+static void test_examples_give_their_answers(void) {
+    check_answer(2, 1, "day-02/examples/example.txt", "8");
+    check_answer(5, 1, "day-05/examples/example.txt", "35");
+    check_answer(5, 2, "day-05/examples/example.txt", "46");
+    check_answer(7, 1, "day-07/examples/example.txt", "6440");
+    check_answer(7, 2, "day-07/examples/example.txt", "5905");
+    check_answer(9, 1, "day-09/examples/example.txt", "114");
+    check_answer(9, 2, "day-09/examples/example.txt", "2");
+}
+
+static void test_a_small_buffer_reports_the_length_needed(void) {
+    const char *input = "32T3K 765\nKTJJT 220\n";
+    size_t len = 0;
+    AocStatus status = aoc_solve(7, 1, (const uint8_t *)input, strlen(input), NULL, &len);
+    CHECK(status == AOC_STATUS_BUFFER_TOO_SMALL, "%s", aoc_status_name(status));
+    CHECK(len == 4, "needs %zu", len);
+
+    char *answer = malloc(len + 1);
+    len += 1;
+    status = aoc_solve(7, 1, (const uint8_t *)input, strlen(input), answer, &len);
+    CHECK(status == AOC_STATUS_OK && strcmp(answer, "1205") == 0, "%s", answer);
+    free(answer);
+}
+
+static void test_parse_errors_say_where(void) {
+    const char *input = "32T3K 765\nKK677 lots\n";
+    char msg[256];
+    size_t len = sizeof msg;
+    AocStatus status = aoc_solve(7, 1, (const uint8_t *)input, strlen(input), msg, &len);
+    CHECK(status == AOC_STATUS_PARSE_ERROR, "%s", aoc_status_name(status));
+    CHECK(strncmp(msg, "day 07, line 2, column 7: ", 26) == 0, "%s", msg);
+    CHECK(strcmp(aoc_status_name(status), "parse error") == 0, "%s", aoc_status_name(status));
+}
+
+static void test_a_part_without_an_answer_says_why(void) {
+    const char *input = "L\n\nAAA = (AAA, AAA)\n";
+    char msg[128];
+    size_t len = sizeof msg;
+    AocStatus status = aoc_solve(8, 1, (const uint8_t *)input, strlen(input), msg, &len);
+    CHECK(status == AOC_STATUS_NO_ANSWER, "%s", aoc_status_name(status));
+    CHECK(strcmp(msg, "day 08, part 1: The map has no node `ZZZ`") == 0, "%s", msg);
+}
+
+static void test_bad_arguments_are_reported(void) {
+    char msg[64];
+    size_t len = sizeof msg;
+    CHECK(aoc_solve(6, 1, NULL, 0, msg, &len) == AOC_STATUS_UNKNOWN_DAY, "%s", msg);
+    CHECK(strcmp(msg, "Day 6 is not implemented") == 0, "%s", msg);
+    len = sizeof msg;
+    CHECK(aoc_solve(7, 3, NULL, 0, msg, &len) == AOC_STATUS_UNKNOWN_PART, "%s", msg);
+    len = sizeof msg;
+    CHECK(aoc_solve(7, 1, (const uint8_t *)"\xff 1\n", 4, msg, &len) == AOC_STATUS_INVALID_UTF8,
+          "%s", msg);
+    CHECK(aoc_solve(7, 1, NULL, 0, msg, NULL) == AOC_STATUS_NULL_POINTER, "no out_len");
+}
+
+static void test_every_day_is_listed(void) {
+    uint8_t days[32];
+    size_t count = aoc_days(days, sizeof days);
+    CHECK(count == aoc_days(NULL, 0), "%zu", count);
+    CHECK(count == 8 && days[0] == 1 && days[5] == 7 && days[7] == 9, "%zu days", count);
+}
+
+int main(void) {
+    test_examples_give_their_answers();
+    test_a_small_buffer_reports_the_length_needed();
+    test_parse_errors_say_where();
+    test_a_part_without_an_answer_says_why();
+    test_bad_arguments_are_reported();
+    test_every_day_is_listed();
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return 1;
+    }
+    printf("All checks passed\n");
+    return 0;
+}
@@ -0,0 +1,180 @@
+//! Python bindings for the day solvers, as the extension module `aoc2023`:
+//!
+//! ```python
+//! import aoc2023
+//!
+//! aoc2023.solve(7, 2, open("input/day-07.txt").read())
+//! aoc2023.predict_next([10, 13, 16, 21, 30, 45])  # 68
+//! aoc2023.Hand("KTJJT").jokers_wild_score()  # "FourOfAKind"
+//! ```
+//!
+//! Inputs that don't parse raise `aoc2023.ParseError`, a `ValueError` that
+//! also carries the `line` and `column` of the problem. A part with no answer
+//! for an input that parsed raises a plain `ValueError`.
+
+// pyo3's generated wrappers convert every `PyResult` error, even a `PyErr`
+#![allow(clippy::useless_conversion)]
+
+use std::time::Duration;
+
+use aoc_common::{budget, ParseError as AocParseError, Solution, SolveError, SpanError};
+use pyo3::{
+    basic::CompareOp,
+    create_exception,
+    exceptions::{PyOverflowError, PyValueError},
+    prelude::*,
+};
+
+create_exception!(
+    aoc2023,
+    ParseError,
+    PyValueError,
+    "An input that does not parse, with the `line` and `column` where it goes wrong."
+);
+
+fn parse_error(py: Python<'_>, err: AocParseError) -> PyErr {
+    let exception = ParseError::new_err(err.render());
+    let value = exception.value_bound(py);
+    for (name, n) in [("line", err.line), ("column", err.column)] {
+        if let Err(err) = value.setattr(name, n) {
+            return err;
+        }
+    }
+    exception
+}
+
+/// A [`SpanError`] from parsing `text` for `S`, located in `text`.
+fn span_error<S: Solution>(py: Python<'_>, err: SpanError, text: &str) -> PyErr {
+    parse_error(py, err.locate(S::DAY, text))
+}
+
+/// The answer to `part` of `day` for `input`.
+#[pyfunction]
+fn solve(py: Python<'_>, day: u8, part: u8, input: &str) -> PyResult<String> {
+    let solver = aoc_days::find(day)
+        .ok_or_else(|| PyValueError::new_err(format!("Day {day} is not implemented")))?;
+    if !(1..=2).contains(&part) {
+        return Err(PyValueError::new_err(format!(
+            "There is no part {part}; parts are 1 and 2"
+        )));
+    }
+    let answers = py
+        .allow_threads(|| (solver.solve)(input, &[part]))
+        .map_err(|err| match err {
+            SolveError::Parse(err) => parse_error(py, err),
+            err => PyValueError::new_err(err.to_string()),
+        })?;
+    Ok(answers.parts[0].answer.clone())
+}
+
+/// The days that can be solved, in order.
+#[pyfunction]
+fn days() -> Vec<u8> {
+    aoc_days::DAYS.iter().map(|day| day.number).collect()
+}
+
+/// Why a day 9 prediction failed; Python's own overflow error, as for values
+/// that don't fit in an i32 to begin with.
+fn prediction_overflows() -> PyErr {
+    PyOverflowError::new_err("The prediction does not fit in an i32")
+}
+
+/// Day 9: the value that comes after `seq`.
+#[pyfunction]
+fn predict_next(seq: Vec<i32>) -> PyResult<i32> {
+    day_09::predict_next(&seq).ok_or_else(prediction_overflows)
+}
+
+/// Day 9: the value that comes before `seq`.
+#[pyfunction]
+fn predict_first(seq: Vec<i32>) -> PyResult<i32> {
+    day_09::predict_first(&seq).ok_or_else(prediction_overflows)
+}
+
+/// Day 7: a hand of five cards such as `Hand("KTJJT")`. Hands compare by
+/// the part 1 rules.
+#[pyclass(module = "aoc2023", frozen)]
+struct Hand(day_07::Hand);
+
+#[pymethods]
+impl Hand {
+    #[new]
+    fn new(py: Python<'_>, cards: &str) -> PyResult<Hand> {
+        cards
+            .parse::<day_07::Hand>()
+            .map(Hand)
+            .map_err(|err| span_error::<day_07::Day07>(py, err, cards))
+    }
+
+    /// The hand's type, such as `"TwoPair"`.
+    fn score(&self) -> String {
+        format!("{:?}", self.0.score())
+    }
+
+    /// The best type the hand makes with its jacks as jokers.
+    fn jokers_wild_score(&self) -> String {
+        format!("{:?}", self.0.jokers_wild_score())
+    }
+
+    /// Compares with `other` by the part 2 rules: -1, 0 or 1.
+    fn cmp_jokers_wild(&self, other: &Hand) -> i8 {
+        self.0.cmp_jokers_wild(&other.0) as i8
+    }
+
+    fn __richcmp__(&self, other: &Hand, op: CompareOp) -> bool {
+        op.matches(self.0.cmp(&other.0))
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Hand('{}')", self.0)
+    }
+}
+
+/// Day 5: the maps of an almanac, from one category to the next.
+#[pyclass(module = "aoc2023", frozen)]
+struct AgMap(day_05::AgMap);
+
+#[pymethods]
+impl AgMap {
+    /// The maps of a whole almanac, seeds and all.
+    #[staticmethod]
+    fn from_almanac(py: Python<'_>, text: &str) -> PyResult<AgMap> {
+        text.parse::<day_05::Almanac>()
+            .map(|almanac| AgMap(almanac.ag_map))
+            .map_err(|err| span_error::<day_05::Day05>(py, err, text))
+    }
+
+    /// Where `value` ends up in `dest` when followed from `source`, or
+    /// `None` if the maps don't lead there.
+    fn convert(&self, source: &str, dest: &str, value: u32) -> PyResult<Option<u32>> {
+        // Maps that go round in a cycle never reach `dest`
+        budget::limit(Some(Duration::from_secs(1)), || {
+            self.0.convert(source, dest, value)
+        })
+        .map_err(|_| {
+            PyValueError::new_err(format!(
+                "{source} never reaches {dest}: the maps form a cycle"
+            ))
+        })
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.mappings_len()
+    }
+}
+
+#[pymodule]
+fn aoc2023(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("ParseError", m.py().get_type_bound::<ParseError>())?;
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(days, m)?)?;
+    m.add_function(wrap_pyfunction!(predict_next, m)?)?;
+    m.add_function(wrap_pyfunction!(predict_first, m)?)?;
+    m.add_class::<Hand>()?;
+    m.add_class::<AgMap>()?;
+    Ok(())
+}
@@ -0,0 +1,10 @@
+#!/bin/sh
+# Builds the aoc2023 extension module and runs its Python tests against it.
+# Usage: aoc-py/test.sh [unittest args...]
+set -eu
+
+cd "$(dirname "$0")/.."
+cargo build -q -p aoc-py --features extension-module
+mkdir -p target/python
+cp target/debug/libaoc2023.so target/python/aoc2023.so
+PYTHONPATH=target/python exec python3 -m unittest discover -s aoc-py/tests "$@"
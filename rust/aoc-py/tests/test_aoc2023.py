@@ -0,0 +1,106 @@
+"""Tests for the aoc2023 extension module; run them with aoc-py/test.sh."""
+
+import pathlib
+import unittest
+
+import aoc2023
+
+RUST = pathlib.Path(__file__).resolve().parents[2]
+
+ALMANAC = (RUST / "day-05" / "examples" / "example.txt").read_text()
+
+
+def examples():
+    """Each example input with its expected answers, as (day, name, text, {part: answer})."""
+    for expected in sorted(RUST.glob("day-*/examples/*.expected")):
+        day = int(expected.parent.parent.name.removeprefix("day-"))
+        answers = {}
+        for line in expected.read_text().splitlines():
+            if line.strip():
+                part, answer = line.split(":", 1)
+                answers[int(part.removeprefix("part_"))] = answer.strip()
+        yield day, expected.stem, expected.with_suffix(".txt").read_text(), answers
+
+
+class SolveTest(unittest.TestCase):
+    def test_every_example_gives_its_expected_answers(self):
+        for day, name, text, answers in examples():
+            for part, answer in answers.items():
+                with self.subTest(day=day, example=name, part=part):
+                    self.assertEqual(aoc2023.solve(day, part, text), answer)
+
+    def test_days_lists_the_implemented_days(self):
+        self.assertEqual(aoc2023.days(), [1, 2, 3, 4, 5, 7, 8, 9])
+
+    def test_unknown_days_and_parts_are_value_errors(self):
+        with self.assertRaisesRegex(ValueError, "Day 6 is not implemented"):
+            aoc2023.solve(6, 1, "")
+        with self.assertRaisesRegex(ValueError, "There is no part 3"):
+            aoc2023.solve(7, 3, "32T3K 765\n")
+
+    def test_a_part_without_an_answer_is_a_value_error(self):
+        with self.assertRaisesRegex(ValueError, "The map has no node `ZZZ`") as caught:
+            aoc2023.solve(8, 1, "L\n\nAAA = (AAA, AAA)\n")
+        self.assertNotIsInstance(caught.exception, aoc2023.ParseError)
+
+    def test_bad_input_raises_parse_error_with_its_position(self):
+        with self.assertRaises(aoc2023.ParseError) as caught:
+            aoc2023.solve(7, 1, "32T3K 765\nKK677 lots\n")
+        self.assertIsInstance(caught.exception, ValueError)
+        self.assertEqual(caught.exception.line, 2)
+        self.assertEqual(caught.exception.column, 7)
+
+
+class PredictTest(unittest.TestCase):
+    def test_values_are_extrapolated_both_ways(self):
+        self.assertEqual(aoc2023.predict_next([10, 13, 16, 21, 30, 45]), 68)
+        self.assertEqual(aoc2023.predict_first([10, 13, 16, 21, 30, 45]), 5)
+
+    def test_values_must_fit_in_32_bits(self):
+        with self.assertRaises(OverflowError):
+            aoc2023.predict_next([1, 2**40])
+
+    def test_predictions_must_fit_in_32_bits(self):
+        with self.assertRaises(OverflowError):
+            aoc2023.predict_next([-(2**31), 2**31 - 1])
+
+
+class HandTest(unittest.TestCase):
+    def test_hands_are_scored_with_and_without_jokers(self):
+        hand = aoc2023.Hand("KTJJT")
+        self.assertEqual(hand.score(), "TwoPair")
+        self.assertEqual(hand.jokers_wild_score(), "FourOfAKind")
+        self.assertEqual(str(hand), "KTJJT")
+        self.assertEqual(repr(hand), "Hand('KTJJT')")
+
+    def test_hands_compare_by_either_rules(self):
+        ktjjt, kk677 = aoc2023.Hand("KTJJT"), aoc2023.Hand("KK677")
+        self.assertLess(ktjjt, kk677)
+        self.assertEqual(sorted([kk677, ktjjt]), [ktjjt, kk677])
+        self.assertEqual(ktjjt.cmp_jokers_wild(kk677), 1)
+        self.assertEqual(ktjjt.cmp_jokers_wild(ktjjt), 0)
+
+    def test_bad_cards_raise_parse_error(self):
+        with self.assertRaises(aoc2023.ParseError) as caught:
+            aoc2023.Hand("KTXJT")
+        self.assertEqual(caught.exception.column, 3)
+
+
+class AgMapTest(unittest.TestCase):
+    def test_values_are_converted_between_categories(self):
+        ag_map = aoc2023.AgMap.from_almanac(ALMANAC)
+        self.assertEqual(len(ag_map), 7)
+        self.assertEqual(ag_map.convert("seed", "soil", 79), 81)
+        self.assertEqual(ag_map.convert("seed", "location", 79), 82)
+
+    def test_unreachable_categories_are_none(self):
+        ag_map = aoc2023.AgMap.from_almanac(ALMANAC)
+        self.assertIsNone(ag_map.convert("location", "seed", 82))
+
+    def test_bad_almanacs_raise_parse_error(self):
+        with self.assertRaises(aoc2023.ParseError):
+            aoc2023.AgMap.from_almanac("seeds: 79 x\n")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,288 @@
+//! Repeated timing of each day's parse and parts, with a history of past runs
+//! and a baseline to compare new runs against.
+
+use std::{collections::BTreeMap, fs, io::Write, path::Path, time::Duration};
+
+use aoc_common::{alloc::Allocs, Answers};
+
+/// Files, inside the input directory, for every run and for the baseline.
+/// Timings depend on the machine, so like inputs they are not committed.
+pub const HISTORY_FILE_NAME: &str = "bench-history.tsv";
+pub const BASELINE_FILE_NAME: &str = "bench-baseline.tsv";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+/// Median and 95th percentile (nearest rank) of a non-empty set of samples.
+pub fn stats(samples: &mut [Duration]) -> Stats {
+    assert!(!samples.is_empty(), "Need at least one sample");
+    samples.sort_unstable();
+    let rank = |percentile: usize| {
+        let rank = (percentile * samples.len()).div_ceil(100);
+        samples[rank.max(1) - 1]
+    };
+    Stats {
+        median: rank(50),
+        p95: rank(95),
+    }
+}
+
+/// The name of each timed step, in the order they run.
+pub const STEPS: [&str; 3] = ["parse", "part_01", "part_02"];
+
+/// Splits repeated runs of a day into one list of samples per step.
+pub fn samples_by_step(runs: &[Answers]) -> [Vec<Duration>; 3] {
+    let mut samples = [Vec::new(), Vec::new(), Vec::new()];
+    for run in runs {
+        samples[0].push(run.parse_time);
+        for part in &run.parts {
+            samples[usize::from(part.part)].push(part.elapsed);
+        }
+    }
+    samples
+}
+
+/// What each step allocated in one counted run.
+pub fn allocs_by_step(run: &Answers) -> [Allocs; 3] {
+    let mut allocs = [run.parse_allocs, Allocs::default(), Allocs::default()];
+    for part in &run.parts {
+        allocs[usize::from(part.part)] = part.allocs;
+    }
+    allocs
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: u8,
+    pub step: String,
+    pub runs: usize,
+    pub stats: Stats,
+}
+
+impl Record {
+    fn to_line(&self, time: u64) -> String {
+        format!(
+            "{time}\t{}\t{}\t{}\t{}\t{}",
+            self.day,
+            self.step,
+            self.runs,
+            self.stats.median.as_nanos(),
+            self.stats.p95.as_nanos()
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Record> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let [_time, day, step, runs, median, p95] = fields[..] else {
+            return None;
+        };
+        let nanos = |s: &str| s.parse().ok().map(Duration::from_nanos);
+        Some(Record {
+            day: day.parse().ok()?,
+            step: step.to_string(),
+            runs: runs.parse().ok()?,
+            stats: Stats {
+                median: nanos(median)?,
+                p95: nanos(p95)?,
+            },
+        })
+    }
+}
+
+/// Appends `records` to the file at `path`, all stamped with `time`.
+pub fn append(path: &Path, records: &[Record], time: u64) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("Could not create {}: {err}", dir.display()))?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| format!("Could not open {}: {err}", path.display()))?;
+    records
+        .iter()
+        .try_for_each(|record| writeln!(file, "{}", record.to_line(time)))
+        .map_err(|err| format!("Could not write {}: {err}", path.display()))
+}
+
+/// Replaces the baseline at `path` with `records`.
+pub fn save_baseline(path: &Path, records: &[Record], time: u64) -> Result<(), String> {
+    let _ = fs::remove_file(path);
+    append(path, records, time)
+}
+
+/// The latest baseline record for each day and step; empty if there is no
+/// baseline yet. Unreadable lines are skipped.
+pub fn load_baseline(path: &Path) -> BTreeMap<(u8, String), Record> {
+    fs::read_to_string(path)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(Record::from_line)
+        .map(|record| ((record.day, record.step.clone()), record))
+        .collect()
+}
+
+/// How much slower (positive) or faster (negative) `current` is than
+/// `baseline`, as a percentage of the baseline median.
+pub fn change_percent(baseline: &Stats, current: &Stats) -> f64 {
+    let base = baseline.median.as_nanos().max(1) as f64;
+    (current.median.as_nanos() as f64 - base) / base * 100.0
+}
+
+/// A duration with a unit that keeps it readable: `850ns`, `12.3µs`,
+/// `4.56ms`, `1.20s`.
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    if nanos < 1e3 {
+        format!("{nanos}ns")
+    } else if nanos < 1e6 {
+        format!("{:.1}µs", nanos / 1e3)
+    } else if nanos < 1e9 {
+        format!("{:.2}ms", nanos / 1e6)
+    } else {
+        format!("{:.2}s", nanos / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use aoc_common::{alloc::Allocs, PartAnswer};
+
+    use super::*;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_micros(v)).collect()
+    }
+
+    #[test]
+    fn stats_use_the_nearest_rank() {
+        let mut samples = micros(&(1..=100).rev().collect::<Vec<_>>());
+        assert_eq!(
+            stats(&mut samples),
+            Stats {
+                median: Duration::from_micros(50),
+                p95: Duration::from_micros(95),
+            }
+        );
+    }
+
+    #[test]
+    fn stats_of_a_single_sample_are_that_sample() {
+        let stats = stats(&mut micros(&[7]));
+        assert_eq!(
+            (stats.median, stats.p95),
+            (Duration::from_micros(7), Duration::from_micros(7))
+        );
+    }
+
+    #[test]
+    fn runs_are_split_by_step() {
+        let run = Answers {
+            day: 7,
+            parse_time: Duration::from_micros(1),
+            parse_allocs: Allocs::default(),
+            parts: vec![
+                PartAnswer {
+                    part: 1,
+                    answer: String::new(),
+                    elapsed: Duration::from_micros(2),
+                    allocs: Allocs::default(),
+                },
+                PartAnswer {
+                    part: 2,
+                    answer: String::new(),
+                    elapsed: Duration::from_micros(3),
+                    allocs: Allocs::default(),
+                },
+            ],
+        };
+        let samples = samples_by_step(&[run.clone(), run]);
+        assert_eq!(samples, [micros(&[1, 1]), micros(&[2, 2]), micros(&[3, 3])]);
+    }
+
+    #[test]
+    fn the_installed_allocator_counts_while_enabled() {
+        // Other tests may allocate at the same time, so only lower bounds hold.
+        aoc_common::alloc::enable(true);
+        let (held, allocs) = aoc_common::alloc::measure(|| {
+            let _dropped = vec![0_u8; 4096];
+            vec![0_u8; 1024]
+        });
+        aoc_common::alloc::enable(false);
+        assert_eq!(held.len(), 1024);
+        assert!(allocs.count >= 2);
+        assert!(allocs.bytes >= 4096 + 1024);
+        assert!(allocs.peak >= 4096);
+    }
+
+    #[test]
+    fn counted_allocations_are_split_by_step() {
+        let allocs = |count| Allocs {
+            count,
+            bytes: count * 8,
+            peak: count * 4,
+        };
+        let run = Answers {
+            day: 3,
+            parse_time: Duration::ZERO,
+            parse_allocs: allocs(1),
+            parts: vec![PartAnswer {
+                part: 2,
+                answer: String::new(),
+                elapsed: Duration::ZERO,
+                allocs: allocs(3),
+            }],
+        };
+        assert_eq!(
+            allocs_by_step(&run),
+            [allocs(1), Allocs::default(), allocs(3)]
+        );
+    }
+
+    #[test]
+    fn a_saved_baseline_replaces_the_previous_one() {
+        let path = env::temp_dir().join(format!("aoc-bench-{}.tsv", std::process::id()));
+        let record = Record {
+            day: 3,
+            step: "part_01".to_string(),
+            runs: 10,
+            stats: Stats {
+                median: Duration::from_nanos(1200),
+                p95: Duration::from_nanos(1500),
+            },
+        };
+        let older = Record {
+            day: 4,
+            ..record.clone()
+        };
+        save_baseline(&path, &[older], 1).unwrap();
+        save_baseline(&path, std::slice::from_ref(&record), 2).unwrap();
+        let baseline = load_baseline(&path);
+        assert_eq!(baseline.len(), 1);
+        assert_eq!(baseline[&(3, "part_01".to_string())], record);
+    }
+
+    #[test]
+    fn changes_are_relative_to_the_baseline_median() {
+        let at = |micros| Stats {
+            median: Duration::from_micros(micros),
+            p95: Duration::from_micros(micros),
+        };
+        assert_eq!(change_percent(&at(100), &at(125)), 25.0);
+        assert_eq!(change_percent(&at(100), &at(50)), -50.0);
+    }
+
+    #[test]
+    fn durations_are_formatted_with_a_readable_unit() {
+        assert_eq!(format_duration(Duration::from_nanos(850)), "850ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3µs");
+        assert_eq!(format_duration(Duration::from_micros(4_560)), "4.56ms");
+        assert_eq!(format_duration(Duration::from_millis(1_200)), "1.20s");
+    }
+}
@@ -0,0 +1,210 @@
+//! Talks to adventofcode.com using the session cookie of a logged in user.
+
+use std::{env, fs, path::PathBuf, time::Duration};
+
+use aoc_common::YEAR;
+
+use crate::submit::{parse_reply, Reply};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The site asks automated tools to identify themselves and whoever runs
+/// them; see [`user_agent`].
+const PROJECT: &str = "github.com/gmrowe/advent-of-code-2023";
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    user_agent: String,
+    agent: ureq::Agent,
+}
+
+/// The session cookie, taken from `AOC_SESSION` or else from the first line
+/// of the `session` config file (see [`config_file`]).
+pub fn find_session() -> Result<String, String> {
+    if let Some(session) = env::var("AOC_SESSION")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+    {
+        return Ok(session.trim().to_string());
+    }
+    let path = config_file("session").ok_or("Neither AOC_SESSION nor HOME is set")?;
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|s| s.lines().next().map(|line| line.trim().to_string()))
+        .filter(|s| !s.is_empty())
+        .ok_or_else(|| {
+            format!(
+                "No session cookie found; set AOC_SESSION or save it to {}",
+                path.display()
+            )
+        })
+}
+
+/// The User-Agent to send: the project, followed by the contact of whoever
+/// runs it from `AOC_USER_AGENT` or else the first line of the `contact`
+/// config file. Without a contact it is just the project.
+pub fn user_agent() -> String {
+    let contact = env::var("AOC_USER_AGENT").ok().or_else(|| {
+        let text = fs::read_to_string(config_file("contact")?).ok()?;
+        text.lines().next().map(str::to_string)
+    });
+    match contact.as_deref().map(str::trim) {
+        Some(contact) if !contact.is_empty() => format!("{PROJECT} by {contact}"),
+        _ => PROJECT.to_string(),
+    }
+}
+
+/// `$AOC_CONFIG_DIR/<name>`, defaulting to `~/.config/aoc/<name>`.
+pub fn config_file(name: &str) -> Option<PathBuf> {
+    let dir = match env::var_os("AOC_CONFIG_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME")?).join(".config/aoc"),
+    };
+    Some(dir.join(name))
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            user_agent: PROJECT.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Sends `user_agent` instead of the bare project URL.
+    pub fn with_user_agent(mut self, user_agent: &str) -> Client {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// A client for the real site, or for `AOC_BASE_URL` when it is set.
+    pub fn from_env() -> Result<Client, String> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        Ok(Client::new(&base_url, &find_session()?).with_user_agent(&user_agent()))
+    }
+
+    fn day_url(&self, day: u8) -> String {
+        format!("{}/{YEAR}/day/{day}", self.base_url)
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent
+            .request(method, url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", &self.user_agent)
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<String, String> {
+        let url = format!("{}/input", self.day_url(day));
+        self.request("GET", &url)
+            .call()
+            .map_err(|err| describe_error(day, err))?
+            .into_string()
+            .map_err(|err| format!("Could not read the input for day {day}: {err}"))
+    }
+
+    pub fn submit_answer(&self, day: u8, part: u8, answer: &str) -> Result<Reply, String> {
+        let url = format!("{}/answer", self.day_url(day));
+        let html = self
+            .request("POST", &url)
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])
+            .map_err(|err| describe_error(day, err))?
+            .into_string()
+            .map_err(|err| format!("Could not read the reply for day {day}: {err}"))?;
+        parse_reply(&html)
+    }
+}
+
+fn describe_error(day: u8, err: ureq::Error) -> String {
+    match err {
+        ureq::Error::Status(404, _) => format!("Day {day} is not unlocked yet"),
+        ureq::Error::Status(400 | 500, _) => {
+            "The site rejected the session cookie; it may have expired".to_string()
+        }
+        ureq::Error::Status(code, _) => format!("The site answered with HTTP {code}"),
+        ureq::Error::Transport(err) => format!("Could not reach the site: {err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mock_server::MockServer, submit::Verdict};
+
+    #[test]
+    fn inputs_are_requested_with_the_session_cookie() {
+        let server = MockServer::start(vec![(200, "1 2 3\n".to_string())]);
+        let client = Client::new(&server.url, "abc123");
+        assert_eq!(client.fetch_input(9), Ok("1 2 3\n".to_string()));
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("GET /2023/day/9/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("\r\nCookie: session=abc123\r\n"));
+    }
+
+    #[test]
+    fn requests_name_the_project_and_only_the_contact_given() {
+        let server = MockServer::start(vec![(200, String::new()), (200, String::new())]);
+        Client::new(&server.url, "abc123").fetch_input(1).unwrap();
+        Client::new(&server.url, "abc123")
+            .with_user_agent("github.com/gmrowe/advent-of-code-2023 by me@example.com")
+            .fetch_input(1)
+            .unwrap();
+
+        let requests = server.requests();
+        assert!(requests[0].contains("\r\nUser-Agent: github.com/gmrowe/advent-of-code-2023\r\n"));
+        assert!(requests[1].contains(
+            "\r\nUser-Agent: github.com/gmrowe/advent-of-code-2023 by me@example.com\r\n"
+        ));
+    }
+
+    #[test]
+    fn a_locked_day_is_reported() {
+        let server = MockServer::start(vec![(404, "Not Found".to_string())]);
+        let client = Client::new(&server.url, "abc123");
+        assert_eq!(
+            client.fetch_input(25),
+            Err("Day 25 is not unlocked yet".to_string())
+        );
+    }
+
+    #[test]
+    fn a_rejected_session_is_reported() {
+        let server = MockServer::start(vec![(400, "Please log in".to_string())]);
+        let client = Client::new(&server.url, "expired");
+        assert!(client
+            .fetch_input(1)
+            .unwrap_err()
+            .contains("session cookie"));
+    }
+
+    #[test]
+    fn answers_are_posted_as_a_form() {
+        let reply = "<article><p>That's the right answer!</p></article>".to_string();
+        let server = MockServer::start(vec![(200, reply)]);
+        let client = Client::new(&server.url, "abc123");
+        let verdict = client.submit_answer(7, 2, "5905").unwrap().verdict;
+        assert_eq!(verdict, Verdict::Correct);
+
+        let requests = server.requests();
+        assert!(requests[0].starts_with("POST /2023/day/7/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=5905"));
+    }
+
+    #[test]
+    fn a_wrong_answer_reply_is_parsed() {
+        let reply = "<p>That's not the right answer; your answer is too low.  \
+                     Please wait one minute before trying again.</p>"
+            .to_string();
+        let server = MockServer::start(vec![(200, reply)]);
+        let client = Client::new(&server.url, "abc123");
+        let reply = client.submit_answer(7, 1, "1").unwrap();
+        assert_eq!(reply.verdict, Verdict::TooLow);
+        assert_eq!(reply.wait_secs, 60);
+    }
+}
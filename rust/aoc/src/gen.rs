@@ -0,0 +1,590 @@
+//! Synthetic puzzle inputs of any size with answers planted by construction,
+//! so the real solvers can be checked far beyond the size of a real input.
+//! The expected answers here never call into a day's own code.
+
+use std::collections::HashSet;
+
+/// SplitMix64: tiny, fast and the same on every platform, so a seed always
+/// gives the same input.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `lo..=hi`.
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        let span = (hi - lo) as u64 + 1;
+        lo + (self.next_u64() % span) as i64
+    }
+
+    pub fn index(&mut self, len: usize) -> usize {
+        self.range(0, len as i64 - 1) as usize
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+
+    /// `count` distinct numbers from `lo..=hi`, in random order.
+    fn distinct(&mut self, count: usize, lo: i64, hi: i64) -> Vec<i64> {
+        let mut pool = (lo..=hi).collect::<Vec<_>>();
+        self.shuffle(&mut pool);
+        pool.truncate(count);
+        pool
+    }
+}
+
+pub struct Generated {
+    pub input: String,
+    pub part_one: String,
+    pub part_two: String,
+}
+
+impl Generated {
+    /// The answers as an `.expected` fixture file.
+    pub fn expected(&self) -> String {
+        format!("part_01: {}\npart_02: {}\n", self.part_one, self.part_two)
+    }
+}
+
+/// Days with a generator.
+pub const GENERATORS: &[u8] = &[2, 4, 7, 8, 9];
+
+/// How often day 7 deals each hand type, from high card to five of a kind.
+pub type Weights = [u32; 7];
+
+/// Parses `--weights`: seven comma-separated numbers, not all zero.
+pub fn parse_weights(text: &str) -> Result<Weights, String> {
+    let weights = text
+        .split(',')
+        .map(|w| {
+            w.trim()
+                .parse::<u32>()
+                .map_err(|err| format!("Cannot parse weight `{w}`: {err}"))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let weights = Weights::try_from(weights).map_err(|weights| {
+        format!(
+            "Expected 7 weights, from high card to five of a kind, not {}",
+            weights.len()
+        )
+    })?;
+    if weights.iter().all(|&w| w == 0) {
+        return Err("At least one weight must be above zero".to_string());
+    }
+    Ok(weights)
+}
+
+/// `weights` only applies to day 7, whose hand types are even without it.
+pub fn generate(
+    day: u8,
+    size: usize,
+    seed: u64,
+    weights: Option<Weights>,
+) -> Result<Generated, String> {
+    let mut rng = Rng::new(seed);
+    let size = size.max(1);
+    if weights.is_some() && day != 7 {
+        return Err("Only day 7 takes weights".to_string());
+    }
+    match day {
+        2 => Ok(day_02(&mut rng, size)),
+        4 => Ok(day_04(&mut rng, size)),
+        7 => day_07(&mut rng, size, weights.unwrap_or([1; 7])),
+        8 => day_08(&mut rng, size),
+        9 => Ok(day_09(&mut rng, size)),
+        _ => {
+            let days = GENERATORS
+                .iter()
+                .map(|day| format!("{day:02}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(format!(
+                "There is no generator for day {day}; try one of {days}"
+            ))
+        }
+    }
+}
+
+/// Games of up to six draws from a bag, each showing some of the colors.
+fn day_02(rng: &mut Rng, games: usize) -> Generated {
+    const COLORS: [&str; 3] = ["red", "green", "blue"];
+    const LIMITS: [i64; 3] = [12, 13, 14];
+    let (mut possible_ids, mut power_sum) = (0, 0);
+    let mut lines = Vec::with_capacity(games);
+    for id in 1..=games {
+        let mut most = [0; 3];
+        let draws = (0..rng.range(1, 6))
+            .map(|_| {
+                let mut colors = [0, 1, 2];
+                rng.shuffle(&mut colors);
+                let shown = rng.range(1, 3) as usize;
+                colors[..shown]
+                    .iter()
+                    .map(|&color| {
+                        let count = rng.range(1, 20);
+                        most[color] = most[color].max(count);
+                        format!("{count} {}", COLORS[color])
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+        if most
+            .iter()
+            .zip(LIMITS)
+            .all(|(&count, limit)| count <= limit)
+        {
+            possible_ids += id;
+        }
+        power_sum += most.iter().product::<i64>();
+        lines.push(format!("Game {id}: {}", draws.join("; ")));
+    }
+    Generated {
+        input: lines.join("\n") + "\n",
+        part_one: possible_ids.to_string(),
+        part_two: power_sum.to_string(),
+    }
+}
+
+/// Cards with a planted number of matches. Most cards win nothing so the
+/// number of copies in part 2 stays far from overflowing, and no card wins
+/// copies past the last card.
+fn day_04(rng: &mut Rng, cards: usize) -> Generated {
+    let column = |numbers: &[i64]| {
+        numbers
+            .iter()
+            .map(|n| format!("{n:>2}"))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let mut matches = Vec::with_capacity(cards);
+    let mut lines = Vec::with_capacity(cards);
+    for id in 1..=cards {
+        let wanted = if rng.range(0, 9) < 7 {
+            0
+        } else {
+            rng.range(1, 4) as usize
+        };
+        let m = wanted.min(cards - id);
+        let numbers = rng.distinct(35 - m, 1, 99);
+        let (winning, others) = numbers.split_at(10);
+        let mut player = winning[..m].to_vec();
+        player.extend_from_slice(&others[..25 - m]);
+        rng.shuffle(&mut player);
+        matches.push(m);
+        lines.push(format!(
+            "Card {id:>3}: {} | {}",
+            column(winning),
+            column(&player)
+        ));
+    }
+
+    let points = matches
+        .iter()
+        .map(|&m| if m == 0 { 0 } else { 1_u64 << (m - 1) })
+        .sum::<u64>();
+    // From the last card back, each card is itself plus everything won by
+    // the cards it wins.
+    let mut won = vec![0_u64; cards];
+    for i in (0..cards).rev() {
+        won[i] = 1 + won[i + 1..=i + matches[i]].iter().sum::<u64>();
+    }
+    Generated {
+        input: lines.join("\n") + "\n",
+        part_one: points.to_string(),
+        part_two: won.iter().sum::<u64>().to_string(),
+    }
+}
+
+const RANKS: &[u8; 13] = b"23456789TJQKA";
+
+/// Card strength, with jokers weakest when `jokers_wild`.
+fn strength(card: u8, jokers_wild: bool) -> usize {
+    match card {
+        b'J' if jokers_wild => 0,
+        card => RANKS.iter().position(|&r| r == card).expect("A valid card") + 1,
+    }
+}
+
+/// 0 for high card up to 6 for five of a kind.
+fn hand_type(cards: &[u8]) -> u8 {
+    let mut counts = RANKS
+        .iter()
+        .map(|r| cards.iter().filter(|&c| c == r).count())
+        .filter(|&count| count > 0)
+        .collect::<Vec<_>>();
+    counts.sort_unstable();
+    match counts[..] {
+        [5] => 6,
+        [1, 4] => 5,
+        [2, 3] => 4,
+        [1, 1, 3] => 3,
+        [1, 2, 2] => 2,
+        [1, 1, 1, 2] => 1,
+        _ => 0,
+    }
+}
+
+/// The best type jokers can make, trying every card they could all stand for.
+fn joker_type(cards: &[u8]) -> u8 {
+    RANKS
+        .iter()
+        .map(|&stand_in| {
+            let cards = cards
+                .iter()
+                .map(|&c| if c == b'J' { stand_in } else { c })
+                .collect::<Vec<_>>();
+            hand_type(&cards)
+        })
+        .max()
+        .expect("There are ranks to try")
+}
+
+/// Distinct hands whose types are drawn in proportion to `weights`; the
+/// counts in each type's pattern are dealt distinct ranks and then shuffled.
+/// A type whose every hand has been dealt is no longer drawn.
+fn day_07(rng: &mut Rng, hands: usize, weights: Weights) -> Result<Generated, String> {
+    const MAX_HANDS: usize = 100_000;
+    if hands > MAX_HANDS {
+        return Err(format!(
+            "At most {MAX_HANDS} distinct hands can be generated"
+        ));
+    }
+    // How many distinct hands there are of each type
+    const HANDS_OF_TYPE: [usize; 7] = [154_440, 171_600, 25_740, 17_160, 1_560, 780, 13];
+    let mut left = HANDS_OF_TYPE;
+    let available = (0..7)
+        .filter(|&kind| weights[kind] > 0)
+        .map(|kind| HANDS_OF_TYPE[kind])
+        .sum::<usize>();
+    if hands > available {
+        return Err(format!(
+            "The weighted hand types only have {available} distinct hands"
+        ));
+    }
+    const PATTERNS: [&[usize]; 7] = [
+        &[1, 1, 1, 1, 1],
+        &[2, 1, 1, 1],
+        &[2, 2, 1],
+        &[3, 1, 1],
+        &[3, 2],
+        &[4, 1],
+        &[5],
+    ];
+    let mut seen = HashSet::with_capacity(hands);
+    let mut bets = Vec::with_capacity(hands);
+    while bets.len() < hands {
+        let weight = |kind: usize| {
+            if left[kind] > 0 {
+                i64::from(weights[kind])
+            } else {
+                0
+            }
+        };
+        let mut pick = rng.range(0, (0..7).map(weight).sum::<i64>() - 1);
+        let kind = (0..7)
+            .find(|&kind| {
+                pick -= weight(kind);
+                pick < 0
+            })
+            .expect("The pick is below the total weight");
+        let pattern = PATTERNS[kind];
+        let ranks = rng.distinct(pattern.len(), 0, 12);
+        let mut cards = pattern
+            .iter()
+            .zip(ranks)
+            .flat_map(|(&count, rank)| std::iter::repeat_n(RANKS[rank as usize], count))
+            .collect::<Vec<_>>();
+        rng.shuffle(&mut cards);
+        if seen.insert(cards.clone()) {
+            left[kind] -= 1;
+            bets.push((cards, rng.range(1, 1000) as u64));
+        }
+    }
+
+    let winnings = |jokers_wild: bool| {
+        let mut ranked = bets
+            .iter()
+            .map(|(cards, bid)| {
+                let kind = if jokers_wild {
+                    joker_type(cards)
+                } else {
+                    hand_type(cards)
+                };
+                let strengths = cards
+                    .iter()
+                    .map(|&c| strength(c, jokers_wild))
+                    .collect::<Vec<_>>();
+                ((kind, strengths), *bid)
+            })
+            .collect::<Vec<_>>();
+        ranked.sort();
+        ranked
+            .iter()
+            .zip(1..)
+            .map(|((_, bid), rank)| bid * rank)
+            .sum::<u64>()
+    };
+    let input = bets
+        .iter()
+        .map(|(cards, bid)| format!("{} {bid}", String::from_utf8_lossy(cards)))
+        .collect::<Vec<_>>();
+    Ok(Generated {
+        input: input.join("\n") + "\n",
+        part_one: winnings(false).to_string(),
+        part_two: winnings(true).to_string(),
+    })
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Up to six ghosts, each walking its own loop. A ghost leaves its `..A`
+/// start, reaches its `..Z` end after a planted number of steps that is a
+/// multiple of the directions' length, and from there repeats the same loop,
+/// so it is on its end node exactly at multiples of that length. Ghost 0
+/// starts at `AAA` and ends at `ZZZ`. Exits that are never followed point at
+/// random nodes.
+fn day_08(rng: &mut Rng, nodes: usize) -> Result<Generated, String> {
+    // Three letters, the last neither A nor Z, leave room for this many.
+    const MAX_NODES: usize = 12_000;
+    if nodes > MAX_NODES {
+        return Err(format!("At most {MAX_NODES} nodes can be generated"));
+    }
+    let dirs = (0..rng.range(3, 16))
+        .map(|_| if rng.range(0, 1) == 0 { 'L' } else { 'R' })
+        .collect::<Vec<_>>();
+    let ghosts = nodes.clamp(1, 6);
+    let target = (nodes / ghosts / dirs.len()).max(1) as u64;
+    let primes = (target.max(2)..)
+        .filter(|&n| (2..).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .take(ghosts)
+        .collect::<Vec<_>>();
+
+    // Middle names end in B..=Y so they never count as a start or an end.
+    let mut names = (0..26 * 26 * 24)
+        .map(|i| {
+            let letter = |n: usize| char::from(b'A' + n as u8);
+            format!(
+                "{}{}{}",
+                letter(i / (26 * 24)),
+                letter(i / 24 % 26),
+                letter(1 + i % 24)
+            )
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    let mut prefixes = (0..26 * 26)
+        .map(|i| {
+            format!(
+                "{}{}",
+                char::from(b'A' + (i / 26) as u8),
+                char::from(b'A' + (i % 26) as u8)
+            )
+        })
+        .filter(|p| p != "AA" && p != "ZZ")
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut prefixes);
+    prefixes.insert(0, "AA".to_string());
+
+    let mut loops = Vec::new();
+    for (ghost, &prime) in primes.iter().enumerate() {
+        let steps = dirs.len() * prime as usize;
+        let mut path = vec![format!("{}A", prefixes[ghost])];
+        for _ in 1..steps {
+            path.push(names.pop().expect("Enough names for every node"));
+        }
+        path.push(format!("{}Z", prefixes[ghost].replace("AA", "ZZ")));
+        loops.push(path);
+    }
+    let all = loops.iter().flatten().cloned().collect::<Vec<_>>();
+
+    let mut lines = Vec::with_capacity(all.len());
+    for path in &loops {
+        let steps = path.len() - 1;
+        for (i, node) in path.iter().enumerate() {
+            // The end node continues like the start node did.
+            let next = &path[if i == steps { 1 } else { i + 1 }];
+            let other = &all[rng.index(all.len())];
+            let (left, right) = match dirs[i % dirs.len()] {
+                'L' => (next, other),
+                _ => (other, next),
+            };
+            lines.push(format!("{node} = ({left}, {right})"));
+        }
+    }
+    rng.shuffle(&mut lines);
+
+    let lengths = loops
+        .iter()
+        .map(|path| (path.len() - 1) as u128)
+        .collect::<Vec<_>>();
+    let lcm = lengths.iter().fold(1, |acc, &n| acc / gcd(acc, n) * n);
+    Ok(Generated {
+        input: format!(
+            "{}\n\n{}\n",
+            dirs.iter().collect::<String>(),
+            lines.join("\n")
+        ),
+        part_one: lengths[0].to_string(),
+        part_two: lcm.to_string(),
+    })
+}
+
+/// Sequences of 21 values of cubic or lower polynomials, whose next and
+/// previous values are found by evaluating the polynomial. Values stay small
+/// enough for the sums to fit in an `i32` up to about 100 000 sequences.
+fn day_09(rng: &mut Rng, sequences: usize) -> Generated {
+    const LEN: i64 = 21;
+    let (mut next_sum, mut first_sum) = (0_i64, 0_i64);
+    let mut lines = Vec::with_capacity(sequences);
+    for _ in 0..sequences {
+        let degree = rng.range(0, 3) as usize;
+        let limits = [100, 10, 3, 1];
+        let coefficients = limits[..=degree]
+            .iter()
+            .map(|&limit| rng.range(-limit, limit))
+            .collect::<Vec<_>>();
+        let eval = |x: i64| coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+        next_sum += eval(LEN);
+        first_sum += eval(-1);
+        let values = (0..LEN).map(|x| eval(x).to_string()).collect::<Vec<_>>();
+        lines.push(values.join(" "));
+    }
+    Generated {
+        input: lines.join("\n") + "\n",
+        part_one: next_sum.to_string(),
+        part_two: first_sum.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_days as days;
+
+    fn check(day: u8, size: usize, seed: u64) {
+        check_answers(day, &generate(day, size, seed, None).unwrap());
+    }
+
+    fn check_answers(day: u8, generated: &Generated) {
+        let answers = (days::find(day).unwrap().solve)(&generated.input, &[1, 2]).unwrap();
+        assert_eq!(
+            answers.parts[0].answer, generated.part_one,
+            "day {day} part 1"
+        );
+        assert_eq!(
+            answers.parts[1].answer, generated.part_two,
+            "day {day} part 2"
+        );
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_input() {
+        assert_eq!(
+            generate(7, 50, 3, None).unwrap().input,
+            generate(7, 50, 3, None).unwrap().input
+        );
+        assert_ne!(
+            generate(7, 50, 3, None).unwrap().input,
+            generate(7, 50, 4, None).unwrap().input
+        );
+    }
+
+    #[test]
+    fn hand_types_follow_the_counts_of_each_rank() {
+        assert_eq!(hand_type(b"32T3K"), 1);
+        assert_eq!(hand_type(b"KK677"), 2);
+        assert_eq!(hand_type(b"T55J5"), 3);
+        assert_eq!(joker_type(b"T55J5"), 5);
+        assert_eq!(joker_type(b"JJJJJ"), 6);
+    }
+
+    #[test]
+    fn day_02_answers_match_the_solver() {
+        (0..5).for_each(|seed| check(2, 200, seed));
+    }
+
+    #[test]
+    fn day_04_answers_match_the_solver() {
+        (0..5).for_each(|seed| check(4, 200, seed));
+    }
+
+    #[test]
+    fn day_07_answers_match_the_solver() {
+        (0..5).for_each(|seed| check(7, 1000, seed));
+    }
+
+    #[test]
+    fn day_08_answers_match_the_solver() {
+        (0..5).for_each(|seed| check(8, 2000, seed));
+    }
+
+    #[test]
+    fn day_09_answers_match_the_solver() {
+        (0..5).for_each(|seed| check(9, 200, seed));
+    }
+
+    #[test]
+    fn a_day_without_a_generator_is_an_error() {
+        assert!(generate(5, 10, 0, None).is_err());
+    }
+
+    fn type_counts(input: &str) -> [usize; 7] {
+        let mut counts = [0; 7];
+        for line in input.lines() {
+            counts[hand_type(&line.as_bytes()[..5]) as usize] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn day_07_hand_types_follow_the_weights() {
+        let even = generate(7, 1000, 0, None).unwrap();
+        assert!(type_counts(&even.input).iter().all(|&count| count > 0));
+
+        let weights = parse_weights("9,1,0,0,0,0,0").unwrap();
+        let skewed = generate(7, 1000, 0, Some(weights)).unwrap();
+        let [high_card, one_pair, rest @ ..] = type_counts(&skewed.input);
+        assert_eq!(rest, [0; 5]);
+        assert!(high_card > 5 * one_pair, "{high_card} to {one_pair}");
+        assert!(one_pair > 0);
+        check_answers(7, &skewed);
+    }
+
+    #[test]
+    fn a_type_that_runs_out_of_hands_is_no_longer_dealt() {
+        let weights = parse_weights("1,0,0,0,0,0,1000").unwrap();
+        let generated = generate(7, 100, 0, Some(weights)).unwrap();
+        let counts = type_counts(&generated.input);
+        assert_eq!((counts[0], counts[6]), (87, 13));
+        assert!(generate(7, 3000, 0, Some(parse_weights("0,0,0,0,1,1,1").unwrap())).is_err());
+    }
+
+    #[test]
+    fn weights_must_be_seven_numbers_not_all_zero() {
+        assert!(parse_weights("1,1,1").is_err());
+        assert!(parse_weights("0,0,0,0,0,0,0").is_err());
+        assert!(parse_weights("1,1,1,1,1,1,x").is_err());
+        assert_eq!(parse_weights("1, 2,3,4,5,6,7"), Ok([1, 2, 3, 4, 5, 6, 7]));
+        assert!(generate(9, 10, 0, Some([1; 7])).is_err());
+    }
+}
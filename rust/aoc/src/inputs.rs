@@ -0,0 +1,115 @@
+//! Puzzle inputs cached on disk, one `day-NN.txt` per day, so each input is
+//! only ever downloaded once.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Where inputs are kept unless `AOC_INPUT_DIR` says otherwise. The directory
+/// is gitignored: inputs are personal and must not be committed.
+pub const DEFAULT_INPUT_DIR: &str = "input";
+
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: impl Into<PathBuf>) -> InputCache {
+        InputCache { dir: dir.into() }
+    }
+
+    pub fn from_env() -> InputCache {
+        let dir = env::var_os("AOC_INPUT_DIR").unwrap_or_else(|| DEFAULT_INPUT_DIR.into());
+        InputCache::new(dir)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(file_name(day))
+    }
+
+    pub fn is_cached(&self, day: u8) -> bool {
+        self.path(day).is_file()
+    }
+
+    /// The cached input for `day`, calling `fetch` and caching its result
+    /// only when there is nothing on disk yet.
+    pub fn get_or_fetch<F>(&self, day: u8, fetch: F) -> Result<String, String>
+    where
+        F: FnOnce() -> Result<String, String>,
+    {
+        let path = self.path(day);
+        if let Ok(input) = fs::read_to_string(&path) {
+            return Ok(input);
+        }
+        let input = fetch()?;
+        fs::create_dir_all(&self.dir)
+            .and_then(|()| fs::write(&path, &input))
+            .map_err(|err| format!("Could not cache input at {}: {err}", path.display()))?;
+        Ok(input)
+    }
+}
+
+/// Name of a day's input inside an input directory, e.g. `day-07.txt`.
+pub fn file_name(day: u8) -> String {
+    format!("day-{day:02}.txt")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{client::Client, mock_server::MockServer};
+
+    fn temp_cache(name: &str) -> InputCache {
+        let dir = env::temp_dir().join(format!("aoc-inputs-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        InputCache::new(dir)
+    }
+
+    #[test]
+    fn inputs_are_named_by_zero_padded_day() {
+        assert_eq!(
+            InputCache::new("input").path(7),
+            Path::new("input/day-07.txt")
+        );
+    }
+
+    #[test]
+    fn a_fetched_input_is_written_to_the_cache() {
+        let cache = temp_cache("fetched");
+        let server = MockServer::start(vec![(200, "0 3 6\n".to_string())]);
+        let client = Client::new(&server.url, "abc123");
+
+        let input = cache.get_or_fetch(9, || client.fetch_input(9)).unwrap();
+        assert_eq!(input, "0 3 6\n");
+        assert_eq!(fs::read_to_string(cache.path(9)).unwrap(), "0 3 6\n");
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[test]
+    fn a_cached_input_is_not_downloaded_again() {
+        let cache = temp_cache("cached");
+        fs::create_dir_all(cache.dir()).unwrap();
+        fs::write(cache.path(4), "cached").unwrap();
+        let server = MockServer::start(vec![(200, "fresh".to_string())]);
+        let client = Client::new(&server.url, "abc123");
+
+        let input = cache.get_or_fetch(4, || client.fetch_input(4)).unwrap();
+        assert_eq!(input, "cached");
+        assert!(server.requests().is_empty());
+    }
+
+    #[test]
+    fn a_failed_download_leaves_nothing_in_the_cache() {
+        let cache = temp_cache("failed");
+        let server = MockServer::start(vec![(404, "Not Found".to_string())]);
+        let client = Client::new(&server.url, "abc123");
+
+        assert!(cache.get_or_fetch(25, || client.fetch_input(25)).is_err());
+        assert!(!cache.is_cached(25));
+    }
+}
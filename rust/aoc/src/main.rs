@@ -0,0 +1,767 @@
+mod bench;
+mod client;
+mod gen;
+mod inputs;
+#[cfg(test)]
+mod mock_server;
+mod pool;
+mod registry;
+mod scaffold;
+mod submit;
+#[cfg(target_os = "linux")]
+mod watch;
+
+#[global_allocator]
+static ALLOCATOR: alloc::CountingAlloc = alloc::CountingAlloc;
+
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    thread,
+    time::{Duration, Instant},
+};
+
+use aoc_common::{
+    alloc,
+    args::Args,
+    budget, err_msg, expand_inputs, load_input, print_answers,
+    trace::{self, TraceFormat},
+    Answers, Format, Input,
+};
+use aoc_days::{self as days, Day};
+use client::Client;
+use inputs::InputCache;
+use registry::Registry;
+use submit::{History, Submission, Verdict};
+
+const USAGE: &str = "\
+Usage: aoc run --day <N> [--part <P>] [--format <F>] [--csv <summary.csv>] [--trace text|json] [--budget <SECS>] [input_filename|dir|-]...
+       aoc run --all [--part <P>] [--format <F>] [--jobs <J>] [--budget <SECS>] [input_dir]
+       aoc fetch --day <N>
+       aoc submit --day <N> --part <P> [input_filename|-]
+       aoc verify [--record] [input_dir]
+       aoc bench [--day <N>] [--runs <R>] [--threshold <PCT>] [--save-baseline] [--allocs] [input_dir]
+       aoc crosscheck [--day <N>] [--part <P>] [input_filename|dir|-]...
+       aoc gen --day <N> --size <S> [--seed <X>] [--weights <W,...>] [--out <input.txt>] [--check]
+       aoc new --day <N>
+       aoc repl --day <N> [input_filename]
+       aoc watch --day <N> [input_filename]
+
+Formats: text (default), json (one object per line), tsv
+
+`run --all` solves every day and part in parallel on --jobs threads (default:
+one per CPU) and, in text format, prints a summary table with the total
+wall-clock time. A day that fails or panics is reported in the table.
+`--budget` gives each part that many seconds; a part that runs over is stopped
+and reported as timed out, with how far it got.
+
+Inputs are cached in $AOC_INPUT_DIR (default `input`). `run --day` without an
+input file uses the cached input, fetching it first if needed. `-` reads stdin;
+a directory stands for every file in it. With several inputs, each one's answers
+are printed in turn and `--csv` writes them all to one summary. `--trace` writes
+the steps a day traces to stderr, as text or as JSON lines. Fetching needs
+the adventofcode.com session cookie in $AOC_SESSION or ~/.config/aoc/session.
+Requests name this project as their User-Agent; put your contact (an email
+address, say) in $AOC_USER_AGENT or ~/.config/aoc/contact to add it, as the
+site asks.
+
+`submit` solves the part and posts the answer. Every submission is recorded in
+submissions.tsv in the input directory, and answers already known to be wrong,
+outside the bounds of earlier replies, or sent while the site asks us to wait
+are refused without contacting the site.
+
+`verify` solves every day with an input and compares each answer to the
+registry in $AOC_ANSWERS (default `answers.toml`), keyed by a hash of the
+input. Answers missing from the registry but accepted by the site are new;
+`--record` saves them. Any mismatch makes the command fail.
+
+`bench` parses and solves each input --runs times (default 100) and reports
+median and p95 timings. Results are appended to bench-history.tsv in the input
+directory and compared with bench-baseline.tsv; a median more than --threshold
+percent (default 10) slower than the baseline is a regression and makes the
+command fail. `--save-baseline` replaces the baseline with this run.
+`--allocs` adds one more run that counts, per step, the allocations, the bytes
+allocated and the peak heap held beyond what was live when the step began.
+
+`crosscheck` solves each input with the main implementation of every part and
+with any alternates the day registers, and fails if they disagree. Without
+--day it checks every day's cached input; an alternate may skip inputs too big
+for it.
+
+`gen` writes a synthetic input of --size lines (nodes for day 8) from --seed
+(default 0) to stdout and its expected answers, worked out independently of the
+solver, to stderr. `--out` writes the input and an .expected file next to it
+instead; `--check` solves the input and fails if an answer differs. Days 2, 4,
+7, 8 and 9 have generators. `--weights` sets how often day 7 deals each hand
+type, as seven numbers from high card to five of a kind (default 1,1,1,1,1,1,1).
+
+`new` creates the crate day-NN in the workspace from a template, with the
+runner, `Solution` stubs, an examples directory and a test module, and
+registers it with the workspace and in aoc-days, the list of days `aoc` runs.
+An existing day is never overwritten.
+
+`repl` parses the input (the cached one by default) once and then reads
+commands from stdin: `part 1` or `part 2` solves a part, `help` lists the
+queries the day offers, such as `card <id>` for day 4, and `quit` leaves.
+
+`watch` (Linux only) builds and runs the day on its input (the cached one by
+default) and its example fixtures, then does so again whenever the day's
+sources, examples or input change, showing how each answer differs from the
+previous run. Stop it with Ctrl-C.";
+
+const VALUE_OPTIONS: &[&str] = &[
+    "--day",
+    "--part",
+    "--format",
+    "--runs",
+    "--threshold",
+    "--csv",
+    "--jobs",
+    "--size",
+    "--seed",
+    "--weights",
+    "--out",
+    "--trace",
+    "--budget",
+];
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1);
+    let command = args.next().unwrap_or_default();
+    let result = Args::parse(args, VALUE_OPTIONS)
+        .map_err(|err| format!("{err}\n{USAGE}"))
+        .and_then(|args| match command.as_str() {
+            "run" => run(&args),
+            "fetch" => fetch(&args),
+            "submit" => submit(&args),
+            "verify" => verify(&args),
+            "bench" => bench_days(&args),
+            "crosscheck" => crosscheck(&args),
+            "gen" => generate(&args),
+            "new" => new_day(&args),
+            "repl" => repl(&args),
+            "watch" => watch(&args),
+            "" => Err(format!("No command given\n{USAGE}")),
+            other => Err(format!("Unknown command `{other}`\n{USAGE}")),
+        });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("ERROR {msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn requested_parts(args: &Args) -> Result<Vec<u8>, String> {
+    match args.parsed_value::<u8>("--part")? {
+        None => Ok(vec![1, 2]),
+        Some(part @ 1..=2) => Ok(vec![part]),
+        Some(part) => Err(format!("There is no part {part}; parts are 1 and 2")),
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    load_input(path).map_err(|err| format!("{}: {}", path.display(), err_msg(&err, "aoc")))
+}
+
+fn requested_budget(args: &Args) -> Result<Option<Duration>, String> {
+    args.parsed_value::<f64>("--budget")?
+        .map(budget::parse_seconds)
+        .transpose()
+}
+
+/// Solves the `parts` of `input`, stopping any part that runs over `budget`.
+fn solve_within(
+    day: &Day,
+    parts: &[u8],
+    input: &str,
+    budget: Option<Duration>,
+) -> Result<Answers, String> {
+    budget::limit(budget, || (day.solve)(input, parts))
+        .map_err(|timeout| timeout.to_string())?
+        .map_err(|err| err.render())
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let parts = requested_parts(args)?;
+    let format = args.parsed_value::<Format>("--format")?.unwrap_or_default();
+    if let Some(header) = format.header() {
+        println!("{header}");
+    }
+
+    if args.flag("--all") {
+        if args.value("--trace").is_some() {
+            return Err("`--trace` needs a single day; use `--day`".to_string());
+        }
+        return run_all(args, &parts, format);
+    }
+    if let Some(trace_format) = args.parsed_value::<TraceFormat>("--trace")? {
+        trace::enable(trace_format);
+    }
+
+    let number = args
+        .parsed_value::<u8>("--day")?
+        .ok_or_else(|| format!("Either `--day` or `--all` is required\n{USAGE}"))?;
+    let day = days::find(number).ok_or_else(|| format!("Day {number} is not implemented"))?;
+    let budget = requested_budget(args)?;
+    if args.positionals.is_empty() {
+        let input = day_input(args, number)?;
+        print_answers(&solve_within(day, &parts, &input, budget)?, format);
+        return Ok(());
+    }
+    let inputs = expand_inputs(&args.positionals).map_err(|err| err_msg(&err, "aoc"))?;
+    let solve = |input: &str| solve_within(day, &parts, input, budget);
+    aoc_common::run_inputs(&inputs, &solve, format, args.value("--csv"))
+}
+
+/// Solves every day with an input in the input directory, one job per day
+/// and part, spread over `--jobs` threads (default: one per CPU). A part that
+/// fails or panics is reported in its row without stopping the others.
+fn run_all(args: &Args, parts: &[u8], format: Format) -> Result<(), String> {
+    let cache = InputCache::from_env();
+    let dir = args
+        .positionals
+        .first()
+        .map_or(cache.dir(), |dir| Path::new(dir));
+    let threads = match args.parsed_value::<usize>("--jobs")? {
+        Some(threads) => threads,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let budget = requested_budget(args)?;
+
+    let mut inputs = Vec::new();
+    for day in days::DAYS {
+        match read(&dir.join(inputs::file_name(day.number))) {
+            Ok(input) => inputs.push((day, input)),
+            Err(msg) => eprintln!("Skipping day {:02}: {msg}", day.number),
+        }
+    }
+    let jobs = inputs
+        .iter()
+        .flat_map(|(day, input)| parts.iter().map(move |&part| (*day, input.as_str(), part)))
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let results = pool::with_quiet_panics(|| {
+        pool::run_jobs(
+            &jobs,
+            threads,
+            |&(day, input, part)| match pool::catch_panic(|| {
+                solve_within(day, &[part], input, budget)
+            }) {
+                Ok(result) => result,
+                Err(msg) => Err(format!("panicked: {msg}")),
+            },
+        )
+    });
+    let wall_time = start.elapsed();
+
+    let mut rows = Vec::new();
+    let mut failures = 0;
+    for (&(day, _, part), result) in jobs.iter().zip(&results) {
+        match result {
+            Ok(answers) => {
+                if format == Format::Text {
+                    let time = answers.parse_time + answers.parts[0].elapsed;
+                    rows.push(vec![
+                        format!("{:02}", day.number),
+                        part.to_string(),
+                        answers.parts[0].answer.clone(),
+                        bench::format_duration(time),
+                        "ok".to_string(),
+                    ]);
+                } else {
+                    print_answers(answers, format);
+                }
+            }
+            Err(msg) => {
+                failures += 1;
+                let summary = msg.lines().next().unwrap_or_default().to_string();
+                if format == Format::Text {
+                    rows.push(vec![
+                        format!("{:02}", day.number),
+                        part.to_string(),
+                        "-".to_string(),
+                        "-".to_string(),
+                        summary,
+                    ]);
+                } else {
+                    eprintln!("ERROR day {:02} part {part}: {summary}", day.number);
+                }
+            }
+        }
+    }
+
+    if format == Format::Text {
+        use pool::Align::{Left, Right};
+        let header = ["day", "part", "answer", "time", "status"];
+        for line in pool::table(&header, &[Right, Right, Left, Right, Left], &rows) {
+            println!("{line}");
+        }
+        let threads = threads.max(1);
+        println!(
+            "Total wall-clock time: {} ({} parts on {threads} thread{})",
+            bench::format_duration(wall_time),
+            jobs.len(),
+            if threads == 1 { "" } else { "s" }
+        );
+    }
+    if failures > 0 {
+        return Err(format!("{failures} of {} parts failed", jobs.len()));
+    }
+    Ok(())
+}
+
+/// The input named on the command line (`-` for stdin), or else the cached
+/// input.
+fn day_input(args: &Args, number: u8) -> Result<String, String> {
+    match args.positionals.first() {
+        Some(arg) => Input::from_arg(arg)
+            .read()
+            .map_err(|err| format!("{arg}: {}", err_msg(&err, "aoc"))),
+        None => {
+            InputCache::from_env().get_or_fetch(number, || Client::from_env()?.fetch_input(number))
+        }
+    }
+}
+
+fn fetch(args: &Args) -> Result<(), String> {
+    let day = args
+        .parsed_value::<u8>("--day")?
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| format!("`--day` between 1 and 25 is required\n{USAGE}"))?;
+    let cache = InputCache::from_env();
+    if cache.is_cached(day) {
+        println!("{} (already cached)", cache.path(day).display());
+        return Ok(());
+    }
+    cache.get_or_fetch(day, || Client::from_env()?.fetch_input(day))?;
+    println!("{}", cache.path(day).display());
+    Ok(())
+}
+
+fn submit(args: &Args) -> Result<(), String> {
+    let number = args
+        .parsed_value::<u8>("--day")?
+        .ok_or_else(|| format!("`--day` is required\n{USAGE}"))?;
+    let part = match args.parsed_value::<u8>("--part")? {
+        Some(part @ 1..=2) => part,
+        _ => return Err(format!("`--part` 1 or 2 is required\n{USAGE}")),
+    };
+    let day = days::find(number).ok_or_else(|| format!("Day {number} is not implemented"))?;
+    let input = day_input(args, number)?;
+    let answers = (day.solve)(&input, &[part]).map_err(|err| err.render())?;
+    let answer = answers.parts[0].answer.clone();
+    println!("{} {answer}", aoc_common::result_label(number, part));
+
+    let mut history = History::load(InputCache::from_env().dir().join(submit::HISTORY_FILE_NAME))?;
+    if let Some(reason) = history.refusal(number, part, &answer, submit::now()) {
+        return Err(format!("Not submitting: {reason}"));
+    }
+    let reply = Client::from_env()?.submit_answer(number, part, &answer)?;
+    history.record(Submission {
+        time: submit::now(),
+        day: number,
+        part,
+        answer,
+        reply,
+    })?;
+    match reply.verdict {
+        Verdict::Correct => {
+            println!("{}", reply.verdict);
+            Ok(())
+        }
+        verdict if reply.wait_secs > 0 => Err(format!(
+            "{verdict}; wait {}s before the next try",
+            reply.wait_secs
+        )),
+        verdict => Err(verdict.to_string()),
+    }
+}
+
+fn verify(args: &Args) -> Result<(), String> {
+    let cache = InputCache::from_env();
+    let dir = args
+        .positionals
+        .first()
+        .map_or(cache.dir(), |dir| Path::new(dir));
+    let history = History::load(cache.dir().join(submit::HISTORY_FILE_NAME))?;
+    let mut registry = Registry::from_env()?;
+    let (mut matched, mut mismatched, mut missing, mut new) = (0, 0, 0, 0);
+
+    for day in days::DAYS {
+        let input = match read(&dir.join(inputs::file_name(day.number))) {
+            Ok(input) => input,
+            Err(msg) => {
+                eprintln!("Skipping day {:02}: {msg}", day.number);
+                continue;
+            }
+        };
+        let hash = registry::input_hash(&input);
+        let answers = match (day.solve)(&input, &[1, 2]) {
+            Ok(answers) => answers,
+            Err(err) => {
+                eprintln!("{}", err.render());
+                mismatched += 1;
+                continue;
+            }
+        };
+        for part in &answers.parts {
+            let label = aoc_common::result_label(day.number, part.part);
+            let answer = &part.answer;
+            match registry.get(day.number, part.part, &hash) {
+                Some(known) if known == answer => {
+                    matched += 1;
+                    println!("{label} ok {answer}");
+                }
+                Some(known) => {
+                    mismatched += 1;
+                    println!("{label} MISMATCH expected {known}, got {answer}");
+                }
+                None if history.accepted(day.number, part.part) == Some(answer.as_str()) => {
+                    new += 1;
+                    println!("{label} new {answer}");
+                    registry.insert(day.number, part.part, &hash, answer);
+                }
+                None => {
+                    missing += 1;
+                    println!("{label} missing {answer}");
+                }
+            }
+        }
+    }
+
+    println!("{matched} ok, {mismatched} mismatched, {missing} missing, {new} new");
+    if new > 0 && args.flag("--record") {
+        registry.save()?;
+    }
+    if mismatched > 0 {
+        return Err(format!(
+            "{mismatched} answer(s) no longer match the registry"
+        ));
+    }
+    Ok(())
+}
+
+fn bench_days(args: &Args) -> Result<(), String> {
+    let runs = args.parsed_value::<usize>("--runs")?.unwrap_or(100).max(1);
+    let threshold = args.parsed_value::<f64>("--threshold")?.unwrap_or(10.0);
+    let days: Vec<&Day> = match args.parsed_value::<u8>("--day")? {
+        Some(number) => {
+            vec![days::find(number).ok_or_else(|| format!("Day {number} is not implemented"))?]
+        }
+        None => days::DAYS.iter().collect(),
+    };
+    let cache = InputCache::from_env();
+    let dir = args
+        .positionals
+        .first()
+        .map_or(cache.dir(), |dir| Path::new(dir));
+    let baseline_path = cache.dir().join(bench::BASELINE_FILE_NAME);
+    let baseline = bench::load_baseline(&baseline_path);
+
+    let count_allocs = args.flag("--allocs");
+
+    let mut records = Vec::new();
+    let mut regressions = 0;
+    let mut header = format!(
+        "{:>3}  {:<7}  {:>9}  {:>9}  {:>9}  {:>8}",
+        "day", "step", "median", "p95", "baseline", "change"
+    );
+    if count_allocs {
+        write!(header, "  {:>8}  {:>9}  {:>9}", "allocs", "bytes", "peak")
+            .expect("Writing to a String cannot fail");
+    }
+    println!("{header}");
+    for day in days {
+        let input = match read(&dir.join(inputs::file_name(day.number))) {
+            Ok(input) => input,
+            Err(msg) => {
+                eprintln!("Skipping day {:02}: {msg}", day.number);
+                continue;
+            }
+        };
+        let results = (0..runs)
+            .map(|_| (day.solve)(&input, &[1, 2]))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| err.render())?;
+        // Counted apart from the timed runs, so counting cannot slow them.
+        let allocs = if count_allocs {
+            alloc::enable(true);
+            let counted = (day.solve)(&input, &[1, 2]);
+            alloc::enable(false);
+            Some(bench::allocs_by_step(&counted.map_err(|err| err.render())?))
+        } else {
+            None
+        };
+        for (i, (step, mut samples)) in bench::STEPS
+            .iter()
+            .zip(bench::samples_by_step(&results))
+            .enumerate()
+        {
+            let record = bench::Record {
+                day: day.number,
+                step: step.to_string(),
+                runs,
+                stats: bench::stats(&mut samples),
+            };
+            let (base, change) = match baseline.get(&(day.number, step.to_string())) {
+                Some(base) => {
+                    let change = bench::change_percent(&base.stats, &record.stats);
+                    let flag = if change > threshold {
+                        regressions += 1;
+                        " REGRESSION"
+                    } else {
+                        ""
+                    };
+                    (
+                        bench::format_duration(base.stats.median),
+                        format!("{change:+.1}%{flag}"),
+                    )
+                }
+                None => ("-".to_string(), "-".to_string()),
+            };
+            let mut line = format!(
+                "{:>3}  {:<7}  {:>9}  {:>9}  {:>9}  {:>8}",
+                format!("{:02}", day.number),
+                step,
+                bench::format_duration(record.stats.median),
+                bench::format_duration(record.stats.p95),
+                base,
+                change
+            );
+            if let Some(allocs) = allocs {
+                let allocs = allocs[i];
+                write!(
+                    line,
+                    "  {:>8}  {:>9}  {:>9}",
+                    allocs.count,
+                    alloc::format_bytes(allocs.bytes),
+                    alloc::format_bytes(allocs.peak)
+                )
+                .expect("Writing to a String cannot fail");
+            }
+            println!("{line}");
+            records.push(record);
+        }
+    }
+
+    let now = submit::now();
+    bench::append(&cache.dir().join(bench::HISTORY_FILE_NAME), &records, now)?;
+    if args.flag("--save-baseline") {
+        bench::save_baseline(&baseline_path, &records, now)?;
+        println!("Saved baseline to {}", baseline_path.display());
+    }
+    if regressions > 0 {
+        return Err(format!(
+            "{regressions} step(s) regressed more than {threshold}% against the baseline"
+        ));
+    }
+    Ok(())
+}
+
+fn crosscheck(args: &Args) -> Result<(), String> {
+    let parts = requested_parts(args)?;
+    let mut inputs = Vec::new();
+    match args.parsed_value::<u8>("--day")? {
+        Some(number) => {
+            let day =
+                days::find(number).ok_or_else(|| format!("Day {number} is not implemented"))?;
+            if args.positionals.is_empty() {
+                inputs.push((day, inputs::file_name(number), day_input(args, number)?));
+            }
+            for input in expand_inputs(&args.positionals).map_err(|err| err_msg(&err, "aoc"))? {
+                let text = input
+                    .read()
+                    .map_err(|err| format!("{}: {}", input.name(), err_msg(&err, "aoc")))?;
+                inputs.push((day, input.name(), text));
+            }
+        }
+        None => {
+            let cache = InputCache::from_env();
+            for day in days::DAYS {
+                if let Ok(text) = read(&cache.path(day.number)) {
+                    inputs.push((day, inputs::file_name(day.number), text));
+                }
+            }
+        }
+    }
+
+    let (mut agreed, mut disagreed, mut failed) = (0, 0, 0);
+    let mut rows = Vec::new();
+    for (day, name, text) in &inputs {
+        let checks = pool::with_quiet_panics(|| {
+            pool::catch_panic(|| (day.crosscheck)(text, &parts))
+                .and_then(|checks| checks.map_err(|err| err.render()))
+        });
+        let checks = match checks {
+            Ok(checks) => checks,
+            Err(msg) => {
+                eprintln!("ERROR {name} day {:02}: {msg}", day.number);
+                failed += 1;
+                continue;
+            }
+        };
+        for check in checks {
+            if check.agrees() {
+                agreed += 1;
+            } else {
+                disagreed += 1;
+            }
+            let main = check.implementations[0].answer.clone();
+            for implementation in &check.implementations {
+                let status = match &implementation.answer {
+                    None => "skipped",
+                    Some(_) if implementation.answer == main => "ok",
+                    Some(_) => "DIFFERS",
+                };
+                rows.push(vec![
+                    name.clone(),
+                    format!("{:02}", check.day),
+                    check.part.to_string(),
+                    implementation.name.to_string(),
+                    implementation.answer.clone().unwrap_or_default(),
+                    bench::format_duration(implementation.elapsed),
+                    status.to_string(),
+                ]);
+            }
+        }
+    }
+
+    use pool::Align::{Left, Right};
+    for line in pool::table(
+        &[
+            "input",
+            "day",
+            "part",
+            "implementation",
+            "answer",
+            "time",
+            "status",
+        ],
+        &[Left, Right, Right, Left, Left, Right, Left],
+        &rows,
+    ) {
+        println!("{line}");
+    }
+    println!("{agreed} agree, {disagreed} disagree, {failed} failed");
+    if disagreed + failed > 0 {
+        return Err(format!(
+            "{disagreed} part(s) disagree and {failed} input(s) failed"
+        ));
+    }
+    Ok(())
+}
+
+fn generate(args: &Args) -> Result<(), String> {
+    let number = args
+        .parsed_value::<u8>("--day")?
+        .ok_or_else(|| format!("`--day` is required\n{USAGE}"))?;
+    let size = args
+        .parsed_value::<usize>("--size")?
+        .ok_or_else(|| format!("`--size` is required\n{USAGE}"))?;
+    let seed = args.parsed_value::<u64>("--seed")?.unwrap_or(0);
+    let weights = args
+        .value("--weights")
+        .map(gen::parse_weights)
+        .transpose()?;
+    let generated = gen::generate(number, size, seed, weights)?;
+
+    if args.flag("--check") {
+        let day = days::find(number).ok_or_else(|| format!("Day {number} is not implemented"))?;
+        let answers = (day.solve)(&generated.input, &[1, 2]).map_err(|err| err.render())?;
+        let expected = [&generated.part_one, &generated.part_two];
+        let mut mismatched = 0;
+        for (part, expected) in answers.parts.iter().zip(expected) {
+            let label = aoc_common::result_label(number, part.part);
+            if &part.answer == expected {
+                println!("{label} ok {expected}");
+            } else {
+                println!("{label} MISMATCH expected {expected}, got {}", part.answer);
+                mismatched += 1;
+            }
+        }
+        if mismatched > 0 {
+            return Err(format!("{mismatched} answers differ from the generator's"));
+        }
+        return Ok(());
+    }
+
+    match args.value("--out") {
+        Some(out) => {
+            let path = Path::new(out);
+            let expected = path.with_extension("expected");
+            fs::write(path, &generated.input)
+                .map_err(|err| format!("Could not write {out}: {err}"))?;
+            fs::write(&expected, generated.expected())
+                .map_err(|err| format!("Could not write {}: {err}", expected.display()))?;
+            println!("{out}\n{}", expected.display());
+        }
+        None => {
+            print!("{}", generated.input);
+            eprint!("{}", generated.expected());
+        }
+    }
+    Ok(())
+}
+
+fn new_day(args: &Args) -> Result<(), String> {
+    let number = args
+        .parsed_value::<u8>("--day")?
+        .ok_or_else(|| format!("`--day` is required\n{USAGE}"))?;
+    let root = scaffold::find_root()?;
+    for path in scaffold::new_day(&root, number)? {
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+fn repl(args: &Args) -> Result<(), String> {
+    let number = args
+        .parsed_value::<u8>("--day")?
+        .ok_or_else(|| format!("`--day` is required\n{USAGE}"))?;
+    let day = days::find(number).ok_or_else(|| format!("Day {number} is not implemented"))?;
+    if args.positionals.iter().any(|arg| arg == "-") {
+        return Err("`repl` reads commands from stdin, so the input must be a file".to_string());
+    }
+    let input = day_input(args, number)?;
+    let stdin = io::stdin();
+    let prompt = stdin.is_terminal();
+    (day.repl)(&input, &mut stdin.lock(), &mut io::stdout().lock(), prompt)
+}
+
+#[cfg(target_os = "linux")]
+fn watch(args: &Args) -> Result<(), String> {
+    let number = args
+        .parsed_value::<u8>("--day")?
+        .ok_or_else(|| format!("`--day` is required\n{USAGE}"))?;
+    days::find(number).ok_or_else(|| format!("Day {number} is not implemented"))?;
+    let input = match args.positionals.first() {
+        Some(path) => PathBuf::from(path),
+        None => {
+            // Make sure the cached input is there to watch
+            day_input(args, number)?;
+            InputCache::from_env().path(number)
+        }
+    };
+    let input = input
+        .canonicalize()
+        .map_err(|err| format!("{}: {err}", input.display()))?;
+    watch::Watch {
+        root: scaffold::find_root()?,
+        day: number,
+        input,
+    }
+    .run()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn watch(_args: &Args) -> Result<(), String> {
+    Err("`watch` needs Linux inotify".to_string())
+}
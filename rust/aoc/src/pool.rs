@@ -0,0 +1,150 @@
+//! Running independent jobs on a fixed set of threads, with a panic in one
+//! job reported as that job's failure instead of taking the others down.
+
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// Runs `work` on every job using up to `threads` threads and returns the
+/// results in the order of `jobs`.
+pub fn run_jobs<T, R, F>(jobs: &[T], threads: usize, work: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..jobs.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(index) else {
+                    return;
+                };
+                let result = work(job);
+                results.lock().expect("Lock is not poisoned")[index] = Some(result);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .expect("Lock is not poisoned")
+        .into_iter()
+        .map(|result| result.expect("Every job ran"))
+        .collect()
+}
+
+/// Calls `f`, turning a panic into an error holding the panic message.
+pub fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| panic_message(&*payload))
+}
+
+/// Replaces the panic hook with a silent one while `f` runs, so a caught
+/// panic is only reported by whoever handles its error.
+pub fn with_quiet_panics<R>(f: impl FnOnce() -> R) -> R {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = f();
+    panic::set_hook(hook);
+    result
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "panicked".to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Lines of a table whose columns are as wide as their widest cell.
+pub fn table(header: &[&str], align: &[Align], rows: &[Vec<String>]) -> Vec<String> {
+    let mut widths = header.iter().map(|h| h.chars().count()).collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .zip(align)
+            .map(|((cell, &width), align)| match align {
+                Align::Left => format!("{cell:<width$}"),
+                Align::Right => format!("{cell:>width$}"),
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+    std::iter::once(line(header.to_vec()))
+        .chain(
+            rows.iter()
+                .map(|row| line(row.iter().map(String::as_str).collect())),
+        )
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn results_keep_the_order_of_the_jobs() {
+        let jobs = (0..50).collect::<Vec<u64>>();
+        assert_eq!(
+            run_jobs(&jobs, 4, |n| n * n),
+            jobs.iter().map(|n| n * n).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn no_jobs_give_no_results() {
+        assert!(run_jobs(&[] as &[u8], 4, |n| *n).is_empty());
+    }
+
+    #[test]
+    fn a_panicking_job_does_not_stop_the_others() {
+        let results = with_quiet_panics(|| {
+            run_jobs(&[1, 0, 3], 2, |&n| {
+                catch_panic(|| {
+                    assert_ne!(n, 0, "zero is not allowed");
+                    n
+                })
+            })
+        });
+        assert_eq!(results[0], Ok(1));
+        assert!(results[1]
+            .as_ref()
+            .unwrap_err()
+            .contains("zero is not allowed"));
+        assert_eq!(results[2], Ok(3));
+    }
+
+    #[test]
+    fn table_columns_fit_their_widest_cell() {
+        let rows = vec![
+            vec!["1".to_string(), "54388".to_string()],
+            vec!["12".to_string(), "ok".to_string()],
+        ];
+        assert_eq!(
+            table(&["day", "answer"], &[Align::Right, Align::Left], &rows),
+            ["day  answer", "  1  54388", " 12  ok"]
+        );
+    }
+}
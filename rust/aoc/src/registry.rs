@@ -0,0 +1,193 @@
+//! Answers known to be right, keyed by day, part and a hash of the input they
+//! were computed from, so a refactor that changes a result is noticed.
+//!
+//! The registry is a small TOML file with one table per part and one key per
+//! input:
+//!
+//! ```toml
+//! [day-07.part-2]
+//! 3f2a9c0d5e7b1846 = "5905"
+//! ```
+
+use std::{collections::BTreeMap, env, fmt::Write, fs, ops::RangeInclusive, path::PathBuf};
+
+/// Where the registry lives unless `AOC_ANSWERS` says otherwise.
+pub const DEFAULT_ANSWERS_FILE: &str = "answers.toml";
+
+/// A stable 64-bit FNV-1a hash of an input, as 16 hex digits. Unlike
+/// `DefaultHasher` it never changes between Rust releases.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{hash:016x}")
+}
+
+type Key = (u8, u8, String);
+
+pub struct Registry {
+    path: PathBuf,
+    answers: BTreeMap<Key, String>,
+}
+
+impl Registry {
+    pub fn from_env() -> Result<Registry, String> {
+        let path = env::var_os("AOC_ANSWERS").unwrap_or_else(|| DEFAULT_ANSWERS_FILE.into());
+        Registry::load(path)
+    }
+
+    /// Reads the registry at `path`; a missing file is an empty registry.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Registry, String> {
+        let path = path.into();
+        let answers = match fs::read_to_string(&path) {
+            Ok(text) => parse(&text).map_err(|err| format!("{}: {err}", path.display()))?,
+            Err(_) => BTreeMap::new(),
+        };
+        Ok(Registry { path, answers })
+    }
+
+    pub fn get(&self, day: u8, part: u8, hash: &str) -> Option<&str> {
+        self.answers
+            .get(&(day, part, hash.to_string()))
+            .map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: u8, hash: &str, answer: &str) {
+        self.answers
+            .insert((day, part, hash.to_string()), answer.to_string());
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        fs::write(&self.path, self.to_toml())
+            .map_err(|err| format!("Could not write {}: {err}", self.path.display()))
+    }
+
+    fn to_toml(&self) -> String {
+        let mut toml = String::new();
+        let mut table = None;
+        for ((day, part, hash), answer) in &self.answers {
+            if table != Some((day, part)) {
+                if table.is_some() {
+                    toml.push('\n');
+                }
+                table = Some((day, part));
+                writeln!(toml, "[day-{day:02}.part-{part}]")
+                    .expect("Writing to a String cannot fail");
+            }
+            let answer = answer.replace('\\', "\\\\").replace('"', "\\\"");
+            writeln!(toml, "{hash} = \"{answer}\"").expect("Writing to a String cannot fail");
+        }
+        toml
+    }
+}
+
+fn parse(text: &str) -> Result<BTreeMap<Key, String>, String> {
+    let mut answers = BTreeMap::new();
+    let mut table = None;
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            table = Some(parse_table(name).map_err(|err| format!("line {line_number}: {err}"))?);
+            continue;
+        }
+        let (day, part) =
+            table.ok_or_else(|| format!("line {line_number}: answer outside any table"))?;
+        let (hash, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("line {line_number}: expected `hash = \"answer\"`"))?;
+        let answer = parse_string(value.trim())
+            .ok_or_else(|| format!("line {line_number}: answers must be quoted strings"))?;
+        answers.insert((day, part, hash.trim().to_string()), answer);
+    }
+    Ok(answers)
+}
+
+/// `day-07.part-2` to `(7, 2)`.
+fn parse_table(name: &str) -> Result<(u8, u8), String> {
+    let bad_name = || format!("bad table name `{name}`");
+    let (day, part) = name.trim().split_once('.').ok_or_else(bad_name)?;
+    let day = day.strip_prefix("day-").ok_or_else(bad_name)?;
+    let part = part.strip_prefix("part-").ok_or_else(bad_name)?;
+    let in_range = |s: &str, what: &str, range: RangeInclusive<u8>| {
+        s.parse::<u8>()
+            .ok()
+            .filter(|n| range.contains(n))
+            .ok_or_else(|| format!("{what} `{s}` in `{name}` is not in {range:?}"))
+    };
+    Ok((
+        in_range(day, "day", 1..=25)?,
+        in_range(part, "part", 1..=2)?,
+    ))
+}
+
+fn parse_string(value: &str) -> Option<String> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut string = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => string.push(chars.next()?),
+            '"' => return None,
+            c => string.push(c),
+        }
+    }
+    Some(string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_hashes_are_stable() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+    }
+
+    #[test]
+    fn answers_survive_a_round_trip_through_toml() {
+        let mut registry = Registry {
+            path: PathBuf::new(),
+            answers: BTreeMap::new(),
+        };
+        registry.insert(7, 2, "00ff", "5905");
+        registry.insert(7, 1, "00ff", "6440");
+        registry.insert(1, 1, "abcd", "say \"hi\"");
+        let toml = registry.to_toml();
+        assert!(toml.starts_with("[day-01.part-1]\nabcd = \"say \\\"hi\\\"\"\n\n[day-07.part-1]"));
+        assert_eq!(parse(&toml), Ok(registry.answers));
+    }
+
+    #[test]
+    fn comments_and_blank_lines_are_ignored() {
+        let answers = parse("# known answers\n\n[day-09.part-1]\n1234 = \"114\"  \n").unwrap();
+        assert_eq!(answers[&(9, 1, "1234".to_string())], "114");
+    }
+
+    #[test]
+    fn malformed_lines_are_reported_with_their_line_number() {
+        assert_eq!(
+            parse("[day-09.part-1]\n1234 = 114\n"),
+            Err("line 2: answers must be quoted strings".to_string())
+        );
+        assert!(parse("1234 = \"114\"")
+            .unwrap_err()
+            .contains("outside any table"));
+        assert!(parse("[day-9]").unwrap_err().contains("bad table name"));
+        assert_eq!(
+            parse("[day-01.part-1]\n\n[day-99.part-7]\n"),
+            Err("line 3: day `99` in `day-99.part-7` is not in 1..=25".to_string())
+        );
+        assert_eq!(
+            parse("[day-07.part-3]\n"),
+            Err("line 1: part `3` in `day-07.part-3` is not in 1..=2".to_string())
+        );
+        assert!(parse("[day-00.part-1]")
+            .unwrap_err()
+            .contains("not in 1..=25"));
+    }
+}
@@ -0,0 +1,257 @@
+//! `aoc new`: a crate for a new day, made from a template and registered
+//! with the workspace and with `aoc` itself.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+const CARGO_TOML: &str = r#"[package]
+name = "day-NN"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+aoc-common = { path = "../aoc-common" }
+
+[build-dependencies]
+aoc-common = { path = "../aoc-common" }
+"#;
+
+const BUILD_RS: &str = "fn main() {
+    aoc_common::generate_example_tests();
+}
+";
+
+const MAIN_RS: &str = "use std::process::ExitCode;
+
+use day_NN::DayNN;
+
+fn main() -> ExitCode {
+    aoc_common::run::<DayNN>()
+}
+";
+
+const LIB_RS: &str = r#"//! Day NN.
+
+#![warn(missing_docs)]
+
+use aoc_common::{Solution, SpanError};
+
+/// The [`Solution`] for day NN: the input is its lines.
+pub struct DayNN;
+
+impl Solution for DayNN {
+    const DAY: u8 = aoc_common::day_number(env!("CARGO_PKG_NAME"));
+
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, SpanError> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part_one(_lines: &Vec<String>) -> Result<String, String> {
+        Err("Part 1 is not solved yet".into())
+    }
+
+    fn part_two(_lines: &Vec<String>) -> Result<String, String> {
+        Err("Part 2 is not solved yet".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_is_parsed_line_by_line() {
+        assert_eq!(DayNN::parse("a\nb\n").unwrap(), ["a", "b"]);
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
+"#;
+
+const EXAMPLES_README: &str = "\
+Each puzzle example is a pair of files: `<name>.txt` with the input and
+`<name>.expected` with a `part_01: answer` and/or `part_02: answer` line for the
+parts it covers. Every pair becomes a test.
+";
+
+/// The workspace directory: the nearest one at or above the current directory
+/// whose Cargo.toml declares a workspace with `aoc` in it.
+pub fn find_root() -> Result<PathBuf, String> {
+    let cwd = env::current_dir().map_err(|err| format!("No current directory: {err}"))?;
+    cwd.ancestors()
+        .find(|dir| {
+            dir.join("aoc").is_dir()
+                && fs::read_to_string(dir.join("Cargo.toml"))
+                    .is_ok_and(|manifest| manifest.contains("[workspace]"))
+        })
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("{} is not inside the Rust workspace", cwd.display()))
+}
+
+/// Creates `day-NN` under `root` and registers it, returning the files
+/// created or changed. Nothing is written if the day already exists.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("There is no day {day}; days are 1 to 25"));
+    }
+    let dir = root.join(format!("day-{day:02}"));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    // Work out every registration first so a failure leaves nothing behind.
+    let registrations = [
+        (
+            root.join("Cargo.toml"),
+            "\"day-",
+            format!("\"day-{day:02}\","),
+        ),
+        (
+            root.join("aoc-days/Cargo.toml"),
+            "day-",
+            format!("day-{day:02} = {{ path = \"../day-{day:02}\" }}"),
+        ),
+        (
+            root.join("aoc-days/src/lib.rs"),
+            "day!(day_",
+            format!("day!(day_{day:02}::Day{day:02}),"),
+        ),
+    ];
+    let mut edits = Vec::new();
+    for (path, prefix, line) in registrations {
+        let text = fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read {}: {err}", path.display()))?;
+        let text = insert_in_order(&text, prefix, day, &line)
+            .map_err(|msg| format!("{}: {msg}", path.display()))?;
+        edits.push((path, text));
+    }
+
+    let number = format!("{day:02}");
+    let files = [
+        ("Cargo.toml", CARGO_TOML),
+        ("build.rs", BUILD_RS),
+        ("src/main.rs", MAIN_RS),
+        ("src/lib.rs", LIB_RS),
+        ("examples/README.md", EXAMPLES_README),
+    ];
+    let mut written = Vec::new();
+    for (name, template) in files {
+        let path = dir.join(name);
+        let parent = path.parent().expect("Template files are in the day");
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("Could not create {}: {err}", parent.display()))?;
+        fs::write(&path, template.replace("NN", &number))
+            .map_err(|err| format!("Could not write {}: {err}", path.display()))?;
+        written.push(path);
+    }
+    for (path, text) in edits {
+        fs::write(&path, text)
+            .map_err(|err| format!("Could not write {}: {err}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// The day a registration line is for, if it is one: the two digits after
+/// `prefix` at the start of the trimmed line.
+fn day_in(line: &str, prefix: &str) -> Option<u8> {
+    line.trim_start()
+        .strip_prefix(prefix)?
+        .get(..2)?
+        .parse()
+        .ok()
+}
+
+/// Adds `new_line` among the lines registering days, keeping them in order
+/// and indented like their neighbours.
+fn insert_in_order(text: &str, prefix: &str, day: u8, new_line: &str) -> Result<String, String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| day_in(line, prefix).map(|d| (i, d)))
+        .collect::<Vec<_>>();
+    if days.iter().any(|&(_, d)| d == day) {
+        return Err(format!("day {day:02} is already registered"));
+    }
+    let &(last, _) = days.last().ok_or("no days are registered")?;
+    let (at, neighbour) = match days.iter().find(|&&(_, d)| d > day) {
+        Some(&(i, _)) => (i, i),
+        None => (last + 1, last),
+    };
+    let indent = &lines[neighbour][..lines[neighbour].len() - lines[neighbour].trim_start().len()];
+    let new_line = format!("{indent}{new_line}");
+    lines.insert(at, &new_line);
+    Ok(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_day_is_registered_in_order() {
+        let text = "members = [\n    \"aoc\",\n    \"day-01\",\n    \"day-09\",\n]\n";
+        assert_eq!(
+            insert_in_order(text, "\"day-", 6, "\"day-06\",").unwrap(),
+            "members = [\n    \"aoc\",\n    \"day-01\",\n    \"day-06\",\n    \"day-09\",\n]\n"
+        );
+        assert_eq!(
+            insert_in_order(text, "\"day-", 10, "\"day-10\",").unwrap(),
+            "members = [\n    \"aoc\",\n    \"day-01\",\n    \"day-09\",\n    \"day-10\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn a_registered_day_is_not_added_twice() {
+        let text = "day-01 = { path = \"../day-01\" }\n";
+        assert!(insert_in_order(text, "day-", 1, "").is_err());
+    }
+
+    #[test]
+    fn a_new_day_is_created_and_registered() {
+        let root = env::temp_dir().join(format!("aoc-new-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc-days/src")).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"day-09\",\n]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc-days/Cargo.toml"),
+            "[dependencies]\nday-09 = { path = \"../day-09\" }\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("aoc-days/src/lib.rs"),
+            "pub const DAYS: &[Day] = &[\n    day!(day_09::Day09),\n];\n",
+        )
+        .unwrap();
+
+        new_day(&root, 10).unwrap();
+        let lib = fs::read_to_string(root.join("day-10/src/lib.rs")).unwrap();
+        assert!(lib.contains("pub struct Day10;"));
+        // A fresh day fails its parts instead of panicking in `aoc run --all`
+        assert!(lib.contains("Err(\"Part 1 is not solved yet\".into())"));
+        assert!(!lib.contains("todo!"));
+        assert!(fs::read_to_string(root.join("aoc-days/src/lib.rs"))
+            .unwrap()
+            .contains("    day!(day_10::Day10),\n];"));
+        assert!(fs::read_to_string(root.join("aoc-days/Cargo.toml"))
+            .unwrap()
+            .ends_with("day-09 = { path = \"../day-09\" }\nday-10 = { path = \"../day-10\" }\n"));
+
+        assert!(new_day(&root, 10).unwrap_err().contains("already exists"));
+        assert!(new_day(&root, 26).is_err());
+    }
+}
@@ -0,0 +1,477 @@
+//! Understanding the site's replies to submitted answers, and the local
+//! history of submissions used to avoid sending answers that are already
+//! known to be wrong.
+
+use std::{
+    fmt, fs,
+    io::Write,
+    ops::RangeInclusive,
+    path::PathBuf,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// File, inside the input directory, that every submission is appended to.
+pub const HISTORY_FILE_NAME: &str = "submissions.tsv";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about which direction.
+    Wrong,
+    /// Not checked because the previous submission was too recent.
+    TooSoon,
+    /// The part is already solved or not unlocked yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::TooSoon => "too_soon",
+            Verdict::WrongLevel => "wrong_level",
+        }
+    }
+
+    fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Verdict, <Verdict as FromStr>::Err> {
+        [
+            Verdict::Correct,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::Wrong,
+            Verdict::TooSoon,
+            Verdict::WrongLevel,
+        ]
+        .into_iter()
+        .find(|v| v.name() == s)
+        .ok_or_else(|| format!("Unknown verdict `{s}`"))
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            Verdict::Correct => "That's the right answer!",
+            Verdict::TooHigh => "Wrong: the answer is too high",
+            Verdict::TooLow => "Wrong: the answer is too low",
+            Verdict::Wrong => "Wrong answer",
+            Verdict::TooSoon => "Submitted too soon after the last answer",
+            Verdict::WrongLevel => "That part is already solved or still locked",
+        };
+        write!(f, "{description}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reply {
+    pub verdict: Verdict,
+    /// Seconds the site asks us to wait before the next submission.
+    pub wait_secs: u64,
+}
+
+/// Reads the verdict out of the HTML page the site returns for a submission.
+pub fn parse_reply(html: &str) -> Result<Reply, String> {
+    let verdict = if html.contains("That's the right answer") {
+        Verdict::Correct
+    } else if html.contains("You gave an answer too recently") {
+        Verdict::TooSoon
+    } else if html.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else if html.contains("That's not the right answer") {
+        if html.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if html.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else {
+        return Err("Could not understand the site's reply".to_string());
+    };
+    Ok(Reply {
+        verdict,
+        wait_secs: parse_wait(html),
+    })
+}
+
+/// Understands both "You have 1m 23s left to wait" and "Please wait one
+/// minute before trying again". A wait it cannot read counts as a minute.
+fn parse_wait(html: &str) -> u64 {
+    if let Some(end) = html.find(" left to wait") {
+        let start = html[..end].rfind("You have ").map_or(end, |i| i + 9);
+        return html[start..end]
+            .split_whitespace()
+            .map(|part| {
+                let n = part.strip_suffix(['h', 'm', 's'])?;
+                let secs = match &part[n.len()..] {
+                    "h" => 3600,
+                    "m" => 60,
+                    _ => 1,
+                };
+                n.parse::<u64>().ok()?.checked_mul(secs)
+            })
+            .sum::<Option<u64>>()
+            .unwrap_or(60);
+    }
+    if let Some(end) = html.find(" before trying again") {
+        let start = html[..end].rfind("wait ").map_or(end, |i| i + 5);
+        let mut words = html[start..end].split_whitespace();
+        let n = match words.next() {
+            Some("one") => 1,
+            Some(n) => n.parse::<u64>().unwrap_or(1),
+            None => 1,
+        };
+        let unit = words.next().unwrap_or("minute");
+        return if unit.starts_with("second") {
+            n
+        } else {
+            n * 60
+        };
+    }
+    0
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub reply: Reply,
+}
+
+impl Submission {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.time,
+            self.day,
+            self.part,
+            self.answer,
+            self.reply.verdict.name(),
+            self.reply.wait_secs
+        )
+    }
+
+    fn from_line(line: &str) -> Result<Submission, String> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let [time, day, part, answer, verdict, wait_secs] = fields[..] else {
+            return Err(format!("Malformed history line: `{line}`"));
+        };
+        let malformed = |err: &dyn fmt::Display| format!("Malformed history line: `{line}`: {err}");
+        let number = |s: &str| s.parse::<u64>().map_err(|err| malformed(&err));
+        let in_range = |s: &str, what: &str, range: RangeInclusive<u8>| {
+            s.parse::<u8>()
+                .ok()
+                .filter(|n| range.contains(n))
+                .ok_or_else(|| malformed(&format!("{what} `{s}` is not in {range:?}")))
+        };
+        Ok(Submission {
+            time: number(time)?,
+            day: in_range(day, "day", 1..=25)?,
+            part: in_range(part, "part", 1..=2)?,
+            answer: answer.to_string(),
+            reply: Reply {
+                verdict: verdict.parse()?,
+                wait_secs: number(wait_secs)?,
+            },
+        })
+    }
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Every answer submitted from this checkout, stored one per line as
+/// `time day part answer verdict wait_secs`, tab separated.
+pub struct History {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn load(path: impl Into<PathBuf>) -> Result<History, String> {
+        let path = path.into();
+        let submissions = match fs::read_to_string(&path) {
+            Ok(text) => text
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(Submission::from_line)
+                .collect::<Result<Vec<_>, _>>()?,
+            Err(_) => Vec::new(),
+        };
+        Ok(History { path, submissions })
+    }
+
+    pub fn record(&mut self, submission: Submission) -> Result<(), String> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| format!("Could not create {}: {err}", dir.display()))?;
+        }
+        fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", submission.to_line()))
+            .map_err(|err| format!("Could not write {}: {err}", self.path.display()))?;
+        self.submissions.push(submission);
+        Ok(())
+    }
+
+    fn for_part(&self, day: u8, part: u8) -> impl Iterator<Item = &Submission> {
+        self.submissions
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+
+    /// The answer the site accepted for a part, if any.
+    pub fn accepted(&self, day: u8, part: u8) -> Option<&str> {
+        self.for_part(day, part)
+            .find(|s| s.reply.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Explains why `answer` should not be sent at time `now`, if it should
+    /// not: the part is solved, the answer is known to be wrong or outside
+    /// the bounds earlier replies gave, or the site asked us to wait.
+    pub fn refusal(&self, day: u8, part: u8, answer: &str, now: u64) -> Option<String> {
+        if let Some(correct) = self.accepted(day, part) {
+            return Some(format!(
+                "Day {day} part {part} is already solved with {correct}"
+            ));
+        }
+        if let Some(wrong) = self
+            .for_part(day, part)
+            .find(|s| s.answer == answer && s.reply.verdict.is_wrong())
+        {
+            return Some(format!(
+                "{answer} was already rejected: {}",
+                wrong.reply.verdict
+            ));
+        }
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |verdict: Verdict| {
+                self.for_part(day, part)
+                    .filter(move |s| s.reply.verdict == verdict)
+                    .filter_map(|s| s.answer.parse::<i128>().ok())
+            };
+            if let Some(high) = bound(Verdict::TooHigh).filter(|&high| value >= high).min() {
+                return Some(format!("{answer} is not below {high}, which was too high"));
+            }
+            if let Some(low) = bound(Verdict::TooLow).filter(|&low| value <= low).max() {
+                return Some(format!("{answer} is not above {low}, which was too low"));
+            }
+        }
+        let wait_until = self
+            .submissions
+            .iter()
+            .map(|s| s.time + s.reply.wait_secs)
+            .max()
+            .unwrap_or(0);
+        if now < wait_until {
+            return Some(format!(
+                "The site asked us to wait; try again in {}s",
+                wait_until - now
+            ));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::*;
+
+    fn reply(verdict: Verdict) -> Reply {
+        Reply {
+            verdict,
+            wait_secs: 0,
+        }
+    }
+
+    fn history(submissions: &[(u8, &str, Verdict)]) -> History {
+        History {
+            path: PathBuf::new(),
+            submissions: submissions
+                .iter()
+                .map(|&(part, answer, verdict)| Submission {
+                    time: 100,
+                    day: 7,
+                    part,
+                    answer: answer.to_string(),
+                    reply: reply(verdict),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn a_correct_reply_is_recognised() {
+        let html =
+            "<article><p>That's the right answer!  You are one gold star closer.</p></article>";
+        assert_eq!(parse_reply(html), Ok(reply(Verdict::Correct)));
+    }
+
+    #[test]
+    fn a_too_high_reply_carries_its_wait() {
+        let html = "<p>That's not the right answer; your answer is too high.  \
+                    Please wait one minute before trying again.</p>";
+        assert_eq!(
+            parse_reply(html),
+            Ok(Reply {
+                verdict: Verdict::TooHigh,
+                wait_secs: 60
+            })
+        );
+    }
+
+    #[test]
+    fn a_too_low_reply_is_recognised() {
+        let html = "<p>That's not the right answer; your answer is too low.  \
+                    Please wait 5 minutes before trying again.</p>";
+        assert_eq!(
+            parse_reply(html),
+            Ok(Reply {
+                verdict: Verdict::TooLow,
+                wait_secs: 300
+            })
+        );
+    }
+
+    #[test]
+    fn a_too_soon_reply_carries_the_time_left() {
+        let html = "<p>You gave an answer too recently; you have to wait after submitting an \
+                    answer before trying again.  You have 1m 23s left to wait.</p>";
+        assert_eq!(
+            parse_reply(html),
+            Ok(Reply {
+                verdict: Verdict::TooSoon,
+                wait_secs: 83
+            })
+        );
+    }
+
+    #[test]
+    fn a_time_left_that_cannot_be_read_counts_as_a_minute() {
+        for left in ["1m 23…", "1m 23\u{a0}s", "½m", "1m 23x", "ms"] {
+            let html =
+                format!("<p>You gave an answer too recently.  You have {left} left to wait.</p>");
+            assert_eq!(parse_wait(&html), 60, "{left}");
+        }
+        let html = "<p>You gave an answer too recently.  You have 2h 5s left to wait.</p>";
+        assert_eq!(parse_wait(html), 7205);
+    }
+
+    #[test]
+    fn a_wrong_level_reply_is_recognised() {
+        let html =
+            "<p>You don't seem to be solving the right level.  Did you already complete it?</p>";
+        assert_eq!(parse_reply(html), Ok(reply(Verdict::WrongLevel)));
+    }
+
+    #[test]
+    fn an_unexpected_page_is_an_error() {
+        assert!(parse_reply("<html>Advent of Code</html>").is_err());
+    }
+
+    #[test]
+    fn submissions_survive_a_round_trip_through_the_history_file() {
+        let path = env::temp_dir().join(format!("aoc-history-{}.tsv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let submission = Submission {
+            time: 1_701_907_200,
+            day: 7,
+            part: 2,
+            answer: "5905".to_string(),
+            reply: Reply {
+                verdict: Verdict::TooLow,
+                wait_secs: 60,
+            },
+        };
+        History::load(&path)
+            .unwrap()
+            .record(submission.clone())
+            .unwrap();
+        assert_eq!(History::load(&path).unwrap().submissions, [submission]);
+    }
+
+    #[test]
+    fn history_lines_with_a_day_or_part_out_of_range_are_rejected() {
+        let line = |day, part| format!("1701907200\t{day}\t{part}\t5905\ttoo_low\t60");
+        assert_eq!(Submission::from_line(&line(7, 2)).unwrap().day, 7);
+        assert_eq!(
+            Submission::from_line(&line(264, 2)),
+            Err(
+                "Malformed history line: `1701907200\t264\t2\t5905\ttoo_low\t60`: \
+                 day `264` is not in 1..=25"
+                    .to_string()
+            )
+        );
+        assert!(Submission::from_line(&line(0, 1)).is_err());
+        assert!(Submission::from_line(&line(7, 3)).is_err());
+        assert!(Submission::from_line(&line(7, -1)).is_err());
+    }
+
+    #[test]
+    fn a_fresh_answer_is_not_refused() {
+        assert_eq!(history(&[]).refusal(7, 1, "6440", 1000), None);
+    }
+
+    #[test]
+    fn a_rejected_answer_is_refused() {
+        let history = history(&[(1, "6440", Verdict::Wrong)]);
+        assert!(history.refusal(7, 1, "6440", 1000).is_some());
+        assert_eq!(history.refusal(7, 2, "6440", 1000), None);
+    }
+
+    #[test]
+    fn a_solved_part_is_refused() {
+        let history = history(&[(1, "6440", Verdict::Correct)]);
+        assert!(history.refusal(7, 1, "6441", 1000).is_some());
+    }
+
+    #[test]
+    fn answers_outside_known_bounds_are_refused() {
+        let history = history(&[(1, "100", Verdict::TooHigh), (1, "10", Verdict::TooLow)]);
+        assert!(history.refusal(7, 1, "150", 1000).is_some());
+        assert!(history.refusal(7, 1, "5", 1000).is_some());
+        assert_eq!(history.refusal(7, 1, "50", 1000), None);
+    }
+
+    #[test]
+    fn submissions_are_throttled_while_the_site_asks_us_to_wait() {
+        let mut history = history(&[]);
+        history.submissions.push(Submission {
+            time: 100,
+            day: 7,
+            part: 1,
+            answer: "1".to_string(),
+            reply: Reply {
+                verdict: Verdict::TooLow,
+                wait_secs: 60,
+            },
+        });
+        assert!(history.refusal(7, 1, "2", 130).is_some());
+        assert_eq!(history.refusal(7, 1, "2", 160), None);
+    }
+}
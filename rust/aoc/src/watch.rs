@@ -0,0 +1,263 @@
+//! `aoc watch`: rebuild and rerun a day whenever its sources, examples or
+//! input change, using Linux inotify.
+
+use std::{
+    ffi::OsString,
+    io,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use inotify::{Events, Inotify, WatchDescriptor, WatchMask};
+
+/// How long to wait for an editor to finish writing before rerunning.
+const SETTLE: Duration = Duration::from_millis(150);
+
+/// One part's answer, as `(part, answer)`.
+pub type PartAnswers = Vec<(u8, String)>;
+
+/// Reads the answers out of a day binary's `--format tsv` output.
+pub fn parse_tsv_answers(stdout: &str) -> PartAnswers {
+    stdout
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let part = fields.get(2)?.parse::<u8>().ok()?;
+            Some((part, fields.get(3)?.to_string()))
+        })
+        .collect()
+}
+
+/// One line per part of `current`, saying how it differs from `previous`.
+/// On the first run there is nothing to compare with.
+pub fn diff_answers(previous: Option<&PartAnswers>, current: &PartAnswers) -> Vec<String> {
+    current
+        .iter()
+        .map(|(part, answer)| {
+            let note = match previous.map(|p| p.iter().find(|(q, _)| q == part)) {
+                None => String::new(),
+                Some(None) => " (new)".to_string(),
+                Some(Some((_, before))) if before == answer => " (unchanged)".to_string(),
+                Some(Some((_, before))) => format!(" (was {before})"),
+            };
+            format!("part {part}: {answer}{note}")
+        })
+        .collect()
+}
+
+/// Whether a change to the file `name` should trigger a rerun. Editors'
+/// swap, backup and hidden files don't count, and in a directory watched for
+/// one file (`only`) nothing else does either.
+pub fn is_relevant(name: &str, only: Option<&str>) -> bool {
+    let scratch = name.starts_with('.') || name.ends_with('~') || name.ends_with(".swp");
+    !scratch && only.is_none_or(|only| name == only)
+}
+
+/// The relevant files named in `events`, given each watch's one file, if any.
+fn changed_files(events: Events, watches: &[(WatchDescriptor, Option<OsString>)]) -> Vec<String> {
+    events
+        .filter_map(|event| {
+            let name = event.name?.to_string_lossy().into_owned();
+            let (_, only) = watches.iter().find(|(wd, _)| *wd == event.wd)?;
+            let only = only.as_ref().map(|only| only.to_string_lossy());
+            is_relevant(&name, only.as_deref()).then_some(name)
+        })
+        .collect()
+}
+
+/// What `aoc watch` watches and reruns.
+pub struct Watch {
+    pub root: PathBuf,
+    pub day: u8,
+    pub input: PathBuf,
+}
+
+impl Watch {
+    fn package(&self) -> String {
+        format!("day-{:02}", self.day)
+    }
+
+    fn cargo(&self, args: &[&str]) -> Command {
+        let mut cargo = Command::new("cargo");
+        cargo
+            .current_dir(&self.root)
+            .args(args)
+            .args(["-q", "-p", &self.package()]);
+        cargo
+    }
+
+    /// Directories to watch, each with the one file in it that matters, if
+    /// only one does.
+    fn watched(&self) -> Vec<(PathBuf, Option<OsString>)> {
+        let crate_dir = self.root.join(self.package());
+        let mut watched = vec![
+            (crate_dir.join("src"), None),
+            (crate_dir.join("examples"), None),
+            (crate_dir.clone(), None),
+        ];
+        let input_dir = self.input.parent().unwrap_or(Path::new(".")).to_path_buf();
+        // An example used as the input is watched with the other examples
+        if !watched.iter().any(|(dir, _)| *dir == input_dir) {
+            watched.push((input_dir, self.input.file_name().map(OsString::from)));
+        }
+        watched.retain(|(dir, _)| dir.is_dir());
+        watched
+    }
+
+    /// Builds the day and solves its input, or says why it couldn't.
+    fn solve(&self) -> Result<PartAnswers, String> {
+        let built = self
+            .cargo(&["build"])
+            .status()
+            .map_err(|err| format!("Could not run cargo: {err}"))?;
+        if !built.success() {
+            return Err("Build failed".to_string());
+        }
+        let input = self.input.to_string_lossy();
+        let output = self
+            .cargo(&["run"])
+            .args(["--", "--format", "tsv", &input])
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|err| format!("Could not run cargo: {err}"))?;
+        if !output.status.success() {
+            return Err(format!("{} failed on {input}", self.package()));
+        }
+        Ok(parse_tsv_answers(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Runs the day's example fixtures, returning cargo's summary line.
+    fn fixtures(&self) -> Result<String, String> {
+        let output = self
+            .cargo(&["test"])
+            .args(["--lib", "examples::"])
+            .output()
+            .map_err(|err| format!("Could not run cargo: {err}"))?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if output.status.success() {
+            let summary = stdout
+                .lines()
+                .find(|line| line.starts_with("test result:"))
+                .unwrap_or("test result: ok");
+            Ok(format!("examples {summary}"))
+        } else {
+            Err(format!("examples failed\n{}", stdout.trim_end()))
+        }
+    }
+
+    fn rerun(&self, previous: &mut Option<PartAnswers>) {
+        match self.solve() {
+            Ok(answers) => {
+                for line in diff_answers(previous.as_ref(), &answers) {
+                    println!("{line}");
+                }
+                *previous = Some(answers);
+            }
+            Err(msg) => println!("ERROR {msg}"),
+        }
+        match self.fixtures() {
+            Ok(summary) => println!("{summary}"),
+            Err(msg) => println!("ERROR {msg}"),
+        }
+    }
+
+    /// Runs the day now and again after every relevant change, forever.
+    pub fn run(&self) -> Result<(), String> {
+        let watch_err = |err: io::Error| format!("inotify: {err}");
+        let mut inotify = Inotify::init().map_err(watch_err)?;
+        let mask = WatchMask::CLOSE_WRITE
+            | WatchMask::CREATE
+            | WatchMask::DELETE
+            | WatchMask::MOVED_FROM
+            | WatchMask::MOVED_TO;
+        let mut watches: Vec<(WatchDescriptor, Option<OsString>)> = Vec::new();
+        for (dir, only) in self.watched() {
+            let wd = inotify
+                .watches()
+                .add(&dir, mask)
+                .map_err(|err| format!("Cannot watch {}: {err}", dir.display()))?;
+            println!("Watching {}", dir.display());
+            watches.push((wd, only));
+        }
+
+        let mut previous = None;
+        self.rerun(&mut previous);
+        let mut buffer = [0; 4096];
+        loop {
+            let events = inotify
+                .read_events_blocking(&mut buffer)
+                .map_err(watch_err)?;
+            let mut changed = changed_files(events, &watches);
+            if changed.is_empty() {
+                continue;
+            }
+            // Editors often write a file in several steps; wait for them to
+            // finish and take the whole burst as one change.
+            loop {
+                thread::sleep(SETTLE);
+                match inotify.read_events(&mut buffer) {
+                    Ok(events) => changed.extend(changed_files(events, &watches)),
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                    Err(err) => return Err(watch_err(err)),
+                }
+            }
+            changed.sort();
+            changed.dedup();
+            println!("\nChanged: {}", changed.join(", "));
+            self.rerun(&mut previous);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(parts: &[(u8, &str)]) -> PartAnswers {
+        parts.iter().map(|&(p, a)| (p, a.to_string())).collect()
+    }
+
+    #[test]
+    fn answers_are_read_from_tsv_output() {
+        let stdout = "year\tday\tpart\tanswer\tparse_ns\tsolve_ns\n\
+                      2023\t7\t1\t6440\t10\t20\n\
+                      2023\t7\t2\t5905\t10\t30\n";
+        assert_eq!(
+            parse_tsv_answers(stdout),
+            answers(&[(1, "6440"), (2, "5905")])
+        );
+    }
+
+    #[test]
+    fn the_first_run_has_nothing_to_compare_with() {
+        let current = answers(&[(1, "6440")]);
+        assert_eq!(diff_answers(None, &current), ["part 1: 6440"]);
+    }
+
+    #[test]
+    fn later_runs_show_what_changed() {
+        let previous = answers(&[(1, "6440")]);
+        let current = answers(&[(1, "6440"), (2, "5905")]);
+        assert_eq!(
+            diff_answers(Some(&previous), &current),
+            ["part 1: 6440 (unchanged)", "part 2: 5905 (new)"]
+        );
+        let fixed = answers(&[(1, "6440"), (2, "5911")]);
+        assert_eq!(
+            diff_answers(Some(&current), &fixed)[1],
+            "part 2: 5911 (was 5905)"
+        );
+    }
+
+    #[test]
+    fn editor_scratch_files_are_ignored() {
+        assert!(is_relevant("lib.rs", None));
+        assert!(!is_relevant(".lib.rs.swp", None));
+        assert!(!is_relevant("lib.rs~", None));
+        assert!(is_relevant("day-07.txt", Some("day-07.txt")));
+        assert!(!is_relevant("day-08.txt", Some("day-07.txt")));
+    }
+}
@@ -0,0 +1,127 @@
+//! Day 1: Trebuchet?! Each line's calibration value is its first and last
+//! digit read as a two-digit number; in part 2, digits may also be spelled
+//! out.
+
+#![warn(missing_docs)]
+
+use aoc_common::{Solution, SpanError};
+
+fn parse_literal_digit(s: &str) -> Option<u32> {
+    const RADIX: u32 = 10;
+    s.starts_with(|c: char| c.is_ascii_digit()).then(|| {
+        s.chars()
+            .next()
+            .and_then(|c| c.to_digit(RADIX))
+            .expect("slice starts with a digit")
+    })
+}
+
+fn extract_first_and_last_digits<F>(s: &str, parse_fn: F) -> Option<u32>
+where
+    F: Fn(&str) -> Option<u32>,
+{
+    let digits = (0..s.len())
+        .filter(|&i| s.is_char_boundary(i))
+        .filter_map(|i| parse_fn(&s[i..]))
+        .collect::<Vec<_>>();
+    let first = digits.first()?;
+    let last = digits.last()?;
+    Some(10 * first + last)
+}
+
+fn parse_text_digit(s: &str) -> Option<u32> {
+    let text_digits = [
+        "", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    text_digits
+        .into_iter()
+        .enumerate()
+        .skip(1)
+        .find(|(_i, text)| s.starts_with(text))
+        .map(|(i, _text)| i as u32)
+}
+
+fn parse_literal_or_text_digit(s: &str) -> Option<u32> {
+    parse_literal_digit(s).or_else(|| parse_text_digit(s))
+}
+
+/// The calibration value of `line` counting only the digits `0` to `9`, or
+/// `None` if it has none.
+pub fn calibration_value(line: &str) -> Option<u32> {
+    extract_first_and_last_digits(line, parse_literal_digit)
+}
+
+/// The calibration value of `line` counting spelled-out digits such as
+/// `two` as well, or `None` if it has no digits at all.
+pub fn calibration_value_with_words(line: &str) -> Option<u32> {
+    extract_first_and_last_digits(line, parse_literal_or_text_digit)
+}
+
+/// Part 1: the sum of every line's [`calibration_value`], or an error naming
+/// the first line without a digit.
+pub fn total_calibration(lines: &[String]) -> Result<u32, String> {
+    sum_calibration(lines, calibration_value)
+}
+
+/// Part 2: the sum of every line's [`calibration_value_with_words`], or an
+/// error naming the first line without a digit, literal or spelled out.
+pub fn total_calibration_with_words(lines: &[String]) -> Result<u32, String> {
+    sum_calibration(lines, calibration_value_with_words)
+}
+
+fn sum_calibration(lines: &[String], value: fn(&str) -> Option<u32>) -> Result<u32, String> {
+    lines.iter().zip(1..).try_fold(0_u32, |sum, (line, n)| {
+        let value = value(line).ok_or_else(|| format!("Line {n} has no digits"))?;
+        sum.checked_add(value)
+            .ok_or_else(|| "The calibration values add up to too much".to_string())
+    })
+}
+
+/// The [`Solution`] for day 1: the input is its lines, each with a digit.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = aoc_common::day_number(env!("CARGO_PKG_NAME"));
+
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, SpanError> {
+        input
+            .lines()
+            .map(|line| {
+                if calibration_value_with_words(line).is_some() {
+                    Ok(line.to_string())
+                } else {
+                    Err(SpanError::at(line, "Line has no digits"))
+                }
+            })
+            .collect()
+    }
+
+    fn part_one(lines: &Vec<String>) -> Result<String, String> {
+        total_calibration(lines).map(|sum| sum.to_string())
+    }
+
+    fn part_two(lines: &Vec<String>) -> Result<String, String> {
+        total_calibration_with_words(lines).map(|sum| sum.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_line_with_only_spelled_out_digits_has_no_part_1_value() {
+        let input = "1abc2\ntwo1nine\neightwothree\n";
+        let err = aoc_common::solve::<Day01>(input, &[1, 2]).unwrap_err();
+        assert_eq!(err.render(), "day 01, part 1: Line 3 has no digits");
+        let answers = aoc_common::solve::<Day01>(input, &[2]).unwrap();
+        assert_eq!(answers.parts[0].answer, "124");
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
@@ -0,0 +1,199 @@
+//! Day 2: Cube Conundrum. Each game draws handfuls of red, green and blue
+//! cubes from a bag; part 1 asks which games the bag could have played and
+//! part 2 how few cubes each game needs.
+
+#![warn(missing_docs)]
+
+use aoc_common::{Solution, SpanError};
+
+/// One game: the cubes of each color shown in each of its trials, in order.
+/// All three lists have one count per trial, zero for a color not shown.
+#[derive(Debug, Clone)]
+pub struct Game {
+    /// The number after `Game`.
+    pub id: usize,
+    /// Red cubes shown in each trial.
+    pub reds: Vec<u32>,
+    /// Green cubes shown in each trial.
+    pub greens: Vec<u32>,
+    /// Blue cubes shown in each trial.
+    pub blues: Vec<u32>,
+}
+
+impl Game {
+    /// Whether a bag holding this many cubes of each color could have played
+    /// the game.
+    pub fn is_possible(&self, reds: u32, greens: u32, blues: u32) -> bool {
+        self.reds.iter().all(|n| *n <= reds)
+            && self.greens.iter().all(|n| *n <= greens)
+            && self.blues.iter().all(|n| *n <= blues)
+    }
+
+    /// The product of the fewest cubes of each color the game needs, wide
+    /// enough that no three counts can overflow it.
+    pub fn power(&self) -> u128 {
+        let max = |cubes: &[u32]| u128::from(cubes.iter().copied().max().unwrap_or(0));
+        max(&self.reds) * max(&self.blues) * max(&self.greens)
+    }
+}
+
+/// Parses a line such as `Game 3: 8 green, 6 blue; 5 blue, 4 red`.
+pub fn parse_game(line: &str) -> Result<Game, SpanError> {
+    let (id, trials) = line
+        .split_once(':')
+        .ok_or_else(|| SpanError::at(line, "Every line has the form <game_id>: <trials>"))?;
+    let id_num = id
+        .split_once(' ')
+        .ok_or_else(|| SpanError::at(id, "Every id has the form 'Game <num>'"))?
+        .1;
+    let id_num = id_num
+        .parse::<usize>()
+        .map_err(|err| SpanError::at(id_num, format!("Cannot parse game id: {err}")))?;
+    let mut games = Game {
+        reds: Vec::new(),
+        greens: Vec::new(),
+        blues: Vec::new(),
+        id: id_num,
+    };
+    for trial in trials.trim().split("; ") {
+        games.reds.push(0);
+        games.greens.push(0);
+        games.blues.push(0);
+        // Trials are a comma separated list of pairs
+        for pair in trial.split(", ") {
+            let (count, color) = pair
+                .split_once(' ')
+                .ok_or_else(|| SpanError::at(pair, "Every pair has the form <count> <color>"))?;
+            let n = count
+                .parse::<u32>()
+                .map_err(|err| SpanError::at(count, format!("Cannot parse count: {err}")))?;
+            let cubes = match color {
+                "red" => &mut games.reds,
+                "green" => &mut games.greens,
+                "blue" => &mut games.blues,
+                _ => return Err(SpanError::at(color, format!("Unknown color `{color}`"))),
+            };
+            let total = cubes.last_mut().expect("nonempty slice");
+            *total = total.checked_add(n).ok_or_else(|| {
+                SpanError::at(pair, format!("Too many {color} cubes in one trial"))
+            })?;
+        }
+    }
+    Ok(games)
+}
+
+/// Part 1: the sum of the ids of the games a bag of 12 red, 13 green and 14
+/// blue cubes could have played, or `None` if it overflows.
+pub fn possible_game_id_sum(games: &[Game]) -> Option<usize> {
+    games
+        .iter()
+        .filter(|g| g.is_possible(12, 13, 14))
+        .try_fold(0_usize, |sum, g| sum.checked_add(g.id))
+}
+
+/// Part 2: the sum of every game's [`Game::power`], or `None` if it
+/// overflows.
+pub fn power_sum(games: &[Game]) -> Option<u128> {
+    games
+        .iter()
+        .try_fold(0_u128, |sum, g| sum.checked_add(g.power()))
+}
+
+/// The [`Solution`] for day 2: the input is one [`Game`] per line.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = aoc_common::day_number(env!("CARGO_PKG_NAME"));
+
+    type Parsed = Vec<Game>;
+
+    fn parse(input: &str) -> Result<Vec<Game>, SpanError> {
+        input.lines().map(parse_game).collect()
+    }
+
+    fn part_one(games: &Vec<Game>) -> Result<String, String> {
+        possible_game_id_sum(games)
+            .map(|sum| sum.to_string())
+            .ok_or_else(|| "The sum of the game ids is too large".to_string())
+    }
+
+    fn part_two(games: &Vec<Game>) -> Result<String, String> {
+        power_sum(games)
+            .map(|sum| sum.to_string())
+            .ok_or_else(|| "The sum of the powers is too large".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{solve, SolveError};
+
+    use super::*;
+
+    #[test]
+    fn a_game_line_can_be_parsed() {
+        let game = parse_game("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red").unwrap();
+        assert_eq!(game.id, 3);
+        assert_eq!(game.reds, [20, 4]);
+        assert_eq!(game.greens, [8, 0]);
+        assert_eq!(game.blues, [6, 5]);
+    }
+
+    #[test]
+    fn a_game_is_possible_when_no_trial_shows_more_than_the_bag_holds() {
+        let game = parse_game("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red").unwrap();
+        assert!(game.is_possible(20, 8, 6));
+        assert!(!game.is_possible(19, 8, 6));
+        assert_eq!(game.power(), 20 * 8 * 6);
+    }
+
+    #[test]
+    fn an_unknown_color_is_reported_where_it_appears() {
+        let input = "Game 1: 3 blue\nGame 2: 8 green, 6 purple\n";
+        let Err(SolveError::Parse(err)) = solve::<Day02>(input, &[1]) else {
+            panic!("The input should not parse");
+        };
+        assert_eq!((err.line, err.column, err.width), (2, 20, 6));
+        assert_eq!(err.message, "Unknown color `purple`");
+    }
+
+    #[test]
+    fn a_line_without_a_colon_is_an_error() {
+        assert!(parse_game("Game 1 3 blue").is_err());
+    }
+
+    #[test]
+    fn a_count_that_is_not_a_number_is_an_error() {
+        let err = parse_game("Game 1: x blue").unwrap_err();
+        assert!(err.message.starts_with("Cannot parse count"));
+    }
+
+    #[test]
+    fn cube_counts_that_overflow_are_an_error() {
+        // Found by the `day_02_parse_game` fuzz target
+        let err = parse_game("Game 6: 3342859263 blue, 3342859263 blue").unwrap_err();
+        assert_eq!(err.message, "Too many blue cubes in one trial");
+    }
+
+    #[test]
+    fn powers_of_large_counts_do_not_overflow() {
+        let input = "Game 1: 100000 red, 100000 blue, 100000 green\n";
+        let answers = solve::<Day02>(input, &[1, 2]).unwrap();
+        assert_eq!(answers.parts[1].answer, "1000000000000000");
+    }
+
+    #[test]
+    fn game_ids_that_overflow_their_sum_are_an_error() {
+        let input = format!("Game {}: 1 red\nGame 1: 1 red\n", usize::MAX);
+        let err = solve::<Day02>(&input, &[1]).unwrap_err();
+        assert_eq!(
+            err.render(),
+            "day 02, part 1: The sum of the game ids is too large"
+        );
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
@@ -0,0 +1,473 @@
+//! Day 3: Gear Ratios. An engine schematic is a grid of digits, symbols and
+//! dots; part numbers are the numbers next to a symbol, and gears are `*`s
+//! next to exactly two of them.
+
+#![warn(missing_docs)]
+
+use std::str::FromStr;
+
+use aoc_common::{
+    repl::{self, Query},
+    Solution, SpanError,
+};
+
+#[derive(Clone, Copy, Debug)]
+enum Elem {
+    Empty,
+    Num(u32),
+    Sym(char),
+}
+
+impl Elem {
+    fn to_char(self) -> char {
+        match self {
+            Elem::Empty => '.',
+            Elem::Num(n) => char::from_digit(n, 10).expect("a digit"),
+            Elem::Sym(c) => c,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct MapNum {
+    value: u32,
+    start_index: usize,
+    length: usize,
+}
+
+/// An engine schematic. Cells are numbered row by row from 0, so the cell in
+/// row `r` and column `c` (both from 0) has index `r * cols() + c`.
+#[derive(Clone, Debug)]
+pub struct Map {
+    symbols: Vec<Elem>,
+    stride: usize,
+}
+
+impl Map {
+    /// The number of rows.
+    pub fn rows(&self) -> usize {
+        self.symbols.len() / self.stride
+    }
+
+    /// The number of columns.
+    pub fn cols(&self) -> usize {
+        self.stride
+    }
+
+    /// The character in the cell with `index`.
+    ///
+    /// # Panics
+    ///
+    /// If the index is outside the map.
+    pub fn cell(&self, index: usize) -> char {
+        self.symbols[index].to_char()
+    }
+
+    fn map_nums(&self) -> Vec<MapNum> {
+        let mut nums = Vec::new();
+        let mut parsing_num = false;
+        let mut num = 0;
+        let mut start_index = 0;
+        for (index, elem) in self.symbols.iter().enumerate() {
+            // If we reach the end of the line when parsing a number
+            // Push the number and reset all flags
+            if index % self.stride == 0 && parsing_num {
+                nums.push(MapNum {
+                    value: num,
+                    start_index,
+                    length: index - start_index,
+                });
+                parsing_num = false;
+                num = 0;
+            }
+
+            match elem {
+                Elem::Empty | Elem::Sym(_) => {
+                    if parsing_num {
+                        nums.push(MapNum {
+                            value: num,
+                            start_index,
+                            length: index - start_index,
+                        });
+                        parsing_num = false;
+                        num = 0;
+                    }
+                }
+
+                Elem::Num(n) => {
+                    if !parsing_num {
+                        start_index = index;
+                    }
+                    parsing_num = true;
+                    num = 10 * num + n;
+                }
+            }
+        }
+        if parsing_num {
+            nums.push(MapNum {
+                value: num,
+                start_index,
+                length: self.symbols.len() - start_index,
+            });
+        }
+        nums
+    }
+
+    /// Every number next to a symbol, diagonals included, in reading order.
+    pub fn part_numbers(&self) -> Vec<u32> {
+        let has_symbol_neighbor = |i| {
+            self.neighbor_indices(i)
+                .into_iter()
+                .any(|n| matches!(self.symbols[n], Elem::Sym(_)))
+        };
+        self.map_nums()
+            .iter()
+            .filter(|n| (n.start_index..n.start_index + n.length).any(has_symbol_neighbor))
+            .map(|n| n.value)
+            .collect()
+    }
+
+    fn is_neighbor(&self, index: usize, map_num: &MapNum) -> bool {
+        self.neighbor_indices(index)
+            .iter()
+            .any(|&j| map_num.start_index <= j && j < map_num.start_index + map_num.length)
+    }
+
+    /// The two numbers next to each `*` that has exactly two.
+    pub fn gears(&self) -> Vec<(u32, u32)> {
+        let nums = self.map_nums();
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| matches!(s, Elem::Sym('*')))
+            .filter_map(|(i, _)| {
+                let adjacent_nums = nums
+                    .iter()
+                    .filter(|n| self.is_neighbor(i, n))
+                    .collect::<Vec<_>>();
+                if adjacent_nums.len() == 2 {
+                    Some((adjacent_nums[0].value, adjacent_nums[1].value))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// The indices of the up to eight cells around the cell with `index`.
+    pub fn neighbor_indices(&self, index: usize) -> Vec<usize> {
+        let num_rows = self.rows();
+        let row = index / self.stride;
+        let col = index % self.stride;
+
+        let mut indices = Vec::new();
+        for r in -1..=1 {
+            for c in -1..=1 {
+                if let (Some(new_row), Some(new_col)) =
+                    (row.checked_add_signed(r), col.checked_add_signed(c))
+                {
+                    if (r != 0 || c != 0) && new_row < num_rows && new_col < self.stride {
+                        indices.push(new_col + new_row * self.stride);
+                    }
+                }
+            }
+        }
+        indices
+    }
+
+    /// The cell at `row` and `col`, counted from 1, with its neighbors as
+    /// they appear in the schematic.
+    fn describe_neighbors(&self, row: usize, col: usize) -> Result<String, String> {
+        let num_rows = self.rows();
+        if !(1..=num_rows).contains(&row) || !(1..=self.stride).contains(&col) {
+            return Err(format!(
+                "Row {row}, column {col} is outside the {num_rows}x{} map",
+                self.stride
+            ));
+        }
+        let index = (row - 1) * self.stride + col - 1;
+        let mut shown = self.neighbor_indices(index);
+        shown.push(index);
+        shown.sort_unstable();
+        let mut lines = vec![format!("Row {row}, column {col} is `{}`", self.cell(index))];
+        for chunk in shown.chunk_by(|a, b| a / self.stride == b / self.stride) {
+            lines.push(chunk.iter().map(|&i| self.cell(i)).collect());
+        }
+        Ok(lines.join("\n"))
+    }
+}
+
+impl FromStr for Map {
+    type Err = SpanError;
+
+    fn from_str(input: &str) -> Result<Self, <Self as FromStr>::Err> {
+        const RADIX: u32 = 10;
+        let mut symbols = Vec::new();
+        let line_len = input
+            .lines()
+            .next()
+            .map(|s| s.len())
+            .filter(|&len| len > 0)
+            .ok_or_else(|| SpanError::at(input, "Map is empty"))?;
+        for line in input.lines() {
+            if line.len() != line_len {
+                return Err(SpanError::at(
+                    line,
+                    format!(
+                        "Line is {} long but the first line is {line_len}",
+                        line.len()
+                    ),
+                ));
+            }
+
+            for (i, ch) in line.char_indices() {
+                let elem = match ch {
+                    '.' => Elem::Empty,
+                    d if d.is_ascii_digit() => {
+                        let digit = d.to_digit(RADIX).expect("d is a digit");
+                        Elem::Num(digit)
+                    }
+                    other if other.is_ascii() => Elem::Sym(other),
+                    other => {
+                        let ch_str = &line[i..i + other.len_utf8()];
+                        return Err(SpanError::at(ch_str, format!("`{other}` is not ASCII")));
+                    }
+                };
+                symbols.push(elem);
+            }
+            // Part numbers are read as u32s, so longer ones are refused here
+            for run in line.split(|ch: char| !ch.is_ascii_digit()) {
+                if !run.is_empty() && run.parse::<u32>().is_err() {
+                    return Err(SpanError::at(run, format!("`{run}` is too large a number")));
+                }
+            }
+        }
+        let stride = symbols.len() / input.lines().count();
+        Ok(Map { symbols, stride })
+    }
+}
+
+/// Part 1: the sum of the [`Map::part_numbers`].
+pub fn part_number_sum(map: &Map) -> u64 {
+    map.part_numbers().into_iter().map(u64::from).sum()
+}
+
+/// Part 2: the sum of the gear ratios, the products of each gear's numbers,
+/// or `None` if it overflows.
+pub fn gear_ratio_sum(map: &Map) -> Option<u64> {
+    map.gears().into_iter().try_fold(0_u64, |sum, (a, b)| {
+        sum.checked_add(u64::from(a) * u64::from(b))
+    })
+}
+
+/// The [`Solution`] for day 3: the input is one [`Map`].
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = aoc_common::day_number(env!("CARGO_PKG_NAME"));
+
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Map, SpanError> {
+        input.parse::<Map>()
+    }
+
+    fn part_one(map: &Map) -> Result<String, String> {
+        Ok(part_number_sum(map).to_string())
+    }
+
+    fn part_two(map: &Map) -> Result<String, String> {
+        gear_ratio_sum(map)
+            .map(|sum| sum.to_string())
+            .ok_or_else(|| "The sum of the gear ratios is too large".to_string())
+    }
+
+    fn queries() -> Vec<Query<Map>> {
+        vec![Query {
+            name: "neighbors",
+            args: "<row> <col>",
+            help: "Show a cell and its neighbors; rows and columns count from 1",
+            run: |map, args| {
+                let row = repl::arg::<usize>(args, 0, "row")?;
+                let col = repl::arg::<usize>(args, 1, "col")?;
+                map.describe_neighbors(row, col)
+            },
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "467..\n...*.\n..35.";
+
+    #[test]
+    fn cells_are_numbered_row_by_row() {
+        let map = Day03::parse(MAP).unwrap();
+        assert_eq!((map.rows(), map.cols()), (3, 5));
+        assert_eq!(map.cell(8), '*');
+        assert_eq!(map.part_numbers(), [467, 35]);
+        assert_eq!(map.gears(), [(467, 35)]);
+    }
+
+    #[test]
+    fn neighbors_show_the_surrounding_cells() {
+        let map = Day03::parse(MAP).unwrap();
+        assert_eq!(
+            map.describe_neighbors(2, 4),
+            Ok("Row 2, column 4 is `*`\n7..\n.*.\n35.".to_string())
+        );
+    }
+
+    #[test]
+    fn neighbors_stop_at_the_edge_of_the_map() {
+        let map = Day03::parse(MAP).unwrap();
+        assert_eq!(
+            map.describe_neighbors(1, 1),
+            Ok("Row 1, column 1 is `4`\n46\n..".to_string())
+        );
+        assert!(map.describe_neighbors(4, 1).is_err());
+        assert!(map.describe_neighbors(1, 0).is_err());
+    }
+
+    #[test]
+    fn numbers_too_large_for_a_u32_are_an_error() {
+        let input = "1..*........\n.99999999999";
+        let err = Day03::parse(input).unwrap_err().locate(Day03::DAY, input);
+        assert_eq!((err.line, err.column, err.width), (2, 2, 11));
+        assert_eq!(err.message, "`99999999999` is too large a number");
+    }
+
+    #[test]
+    fn large_gear_ratios_do_not_overflow() {
+        let map = Day03::parse("4294967295*4294967295").unwrap();
+        assert_eq!(part_number_sum(&map), 2 * 4_294_967_295);
+        assert_eq!(gear_ratio_sum(&map), Some(4_294_967_295 * 4_294_967_295));
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// A rectangular schematic of dots, digits and symbols. Digit runs are
+    /// cut to three digits, as in the puzzle, so gear ratios fit in a `u32`.
+    fn schematic() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        (1..12_usize, 1..12_usize).prop_flat_map(|(rows, cols)| {
+            let cell = prop_oneof![
+                4 => Just(b'.'),
+                4 => b'0'..=b'9',
+                1 => prop::sample::select(b"*#+$/=%@&-".to_vec()),
+            ];
+            prop::collection::vec(prop::collection::vec(cell, cols), rows).prop_map(|mut grid| {
+                for row in &mut grid {
+                    let mut run = 0;
+                    for cell in row.iter_mut() {
+                        run = if cell.is_ascii_digit() { run + 1 } else { 0 };
+                        if run > 3 {
+                            *cell = b'.';
+                            run = 0;
+                        }
+                    }
+                }
+                grid
+            })
+        })
+    }
+
+    fn text(grid: &[Vec<u8>]) -> String {
+        grid.iter()
+            .map(|row| String::from_utf8(row.clone()).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Every number as `(value, row, first column, last column)`.
+    fn numbers(grid: &[Vec<u8>]) -> Vec<(u32, usize, usize, usize)> {
+        let mut numbers = Vec::new();
+        for (r, row) in grid.iter().enumerate() {
+            let mut c = 0;
+            while c < row.len() {
+                if row[c].is_ascii_digit() {
+                    let start = c;
+                    while c < row.len() && row[c].is_ascii_digit() {
+                        c += 1;
+                    }
+                    let value = std::str::from_utf8(&row[start..c])
+                        .unwrap()
+                        .parse()
+                        .unwrap();
+                    numbers.push((value, r, start, c - 1));
+                } else {
+                    c += 1;
+                }
+            }
+        }
+        numbers
+    }
+
+    fn touches(number: &(u32, usize, usize, usize), r: usize, c: usize) -> bool {
+        let &(_, row, first, last) = number;
+        r + 1 >= row && r <= row + 1 && c + 1 >= first && c <= last + 1
+    }
+
+    fn symbols(grid: &[Vec<u8>]) -> impl Iterator<Item = (usize, usize, u8)> + '_ {
+        grid.iter().enumerate().flat_map(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, &b)| b != b'.' && !b.is_ascii_digit())
+                .map(move |(c, &b)| (r, c, b))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn neighbors_are_in_bounds_and_symmetric(grid in schematic()) {
+            let map = text(&grid).parse::<Map>().unwrap();
+            for i in 0..map.symbols.len() {
+                let neighbors = map.neighbor_indices(i);
+                prop_assert!(neighbors.len() <= 8);
+                for j in neighbors {
+                    prop_assert!(j < map.symbols.len() && j != i);
+                    prop_assert!(map.neighbor_indices(j).contains(&i));
+                }
+            }
+        }
+
+        #[test]
+        fn part_numbers_are_the_numbers_next_to_a_symbol(grid in schematic()) {
+            let expected = numbers(&grid)
+                .iter()
+                .filter(|n| symbols(&grid).any(|(r, c, _)| touches(n, r, c)))
+                .map(|n| n.0)
+                .sum::<u32>();
+            let map = Day03::parse(&text(&grid)).unwrap();
+            prop_assert_eq!(Day03::part_one(&map), Ok(expected.to_string()));
+        }
+
+        #[test]
+        fn gears_are_stars_next_to_exactly_two_numbers(grid in schematic()) {
+            let numbers = numbers(&grid);
+            let expected = symbols(&grid)
+                .filter(|&(_, _, b)| b == b'*')
+                .map(|(r, c, _)| {
+                    let adjacent = numbers.iter().filter(|n| touches(n, r, c)).collect::<Vec<_>>();
+                    match adjacent[..] {
+                        [a, b] => a.0 * b.0,
+                        _ => 0,
+                    }
+                })
+                .sum::<u32>();
+            let map = Day03::parse(&text(&grid)).unwrap();
+            prop_assert_eq!(Day03::part_two(&map), Ok(expected.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
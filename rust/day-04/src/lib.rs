@@ -0,0 +1,425 @@
+//! Day 4: Scratchcards. Each card scores by how many of its numbers are
+//! winning numbers; in part 2, matches win copies of the cards that follow.
+
+#![warn(missing_docs)]
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use aoc_common::{
+    repl::{self, Query},
+    Solution, SpanError,
+};
+
+/// One scratchcard, parsed from a line such as
+/// `Card 1: 41 48 83 | 83 86 6 48`.
+#[derive(Debug)]
+pub struct GameCard {
+    /// The numbers after the bar.
+    pub player_numbers: Vec<u32>,
+    /// The numbers before the bar.
+    pub winning_numbers: Vec<u32>,
+    /// The card number.
+    pub id: usize,
+}
+
+impl GameCard {
+    /// How many of the player's numbers are winning numbers.
+    pub fn match_count(&self) -> usize {
+        let player = self.player_numbers.iter().collect::<HashSet<_>>();
+        let winning = self.winning_numbers.iter().collect::<HashSet<_>>();
+        player.intersection(&winning).count()
+    }
+
+    /// One point for the first match, doubled for each match after it, or
+    /// `None` for more than 32 matches, whose score does not fit in a `u32`.
+    pub fn score(&self) -> Option<u32> {
+        let matching = self.match_count();
+        if matching == 0 {
+            Some(0)
+        } else {
+            let exponent = u32::try_from(matching - 1).ok()?;
+            2_u32.checked_pow(exponent)
+        }
+    }
+}
+
+fn parse_numbers(numbers: &str) -> Result<Vec<u32>, SpanError> {
+    numbers
+        .split_whitespace()
+        .map(|n| {
+            n.parse::<u32>()
+                .map_err(|err| SpanError::at(n, format!("Could not parse number: {err}")))
+        })
+        .collect()
+}
+
+impl FromStr for GameCard {
+    type Err = SpanError;
+
+    fn from_str(s: &str) -> Result<Self, <Self as FromStr>::Err> {
+        let (id, numbers) = s
+            .split_once(':')
+            .ok_or_else(|| SpanError::at(s, "No colon found in string"))?;
+        let id = id
+            .split_whitespace()
+            .nth(1)
+            .ok_or_else(|| SpanError::at(id, "Game id malformed"))?;
+        let id = id
+            .parse::<usize>()
+            .map_err(|err| SpanError::at(id, format!("Could not parse id to usize: {err}")))?;
+        let (winning, player) = numbers
+            .split_once('|')
+            .ok_or_else(|| SpanError::at(numbers, "No bar found in numbers"))?;
+        Ok(GameCard {
+            winning_numbers: parse_numbers(winning)?,
+            player_numbers: parse_numbers(player)?,
+            id,
+        })
+    }
+}
+
+/// Part 1: the sum of every card's [`GameCard::score`], or `None` if a score
+/// or the sum overflows.
+pub fn total_points(game_cards: &[GameCard]) -> Option<u64> {
+    game_cards
+        .iter()
+        .try_fold(0_u64, |sum, gc| sum.checked_add(u64::from(gc.score()?)))
+}
+
+/// Part 2: how many cards there are once every card has won its copies. A
+/// card with `n` matches wins one copy of each of the next `n` cards, once for
+/// every copy of it. `None` if the count overflows, as copies can double with
+/// every card.
+///
+/// # Panics
+///
+/// If the cards are not numbered from 1 in order, as [`Day04::parse`]
+/// checks.
+pub fn total_cards(game_cards: &[GameCard]) -> Option<usize> {
+    let mut copies = vec![1_usize; game_cards.len() + 1];
+    copies[0] = 0;
+    for gc in game_cards.iter() {
+        let id_copies = copies[gc.id];
+        aoc_common::trace!(
+            "copies",
+            card = gc.id,
+            copies = id_copies,
+            matches = gc.match_count()
+        );
+        for id in copies.iter_mut().skip(gc.id + 1).take(gc.match_count()) {
+            *id = id.checked_add(id_copies)?;
+        }
+    }
+    copies
+        .iter()
+        .try_fold(0_usize, |sum, &n| sum.checked_add(n))
+}
+
+/// The [`Solution`] for day 4: the input is one [`GameCard`] per line,
+/// numbered from 1.
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = aoc_common::day_number(env!("CARGO_PKG_NAME"));
+
+    type Parsed = Vec<GameCard>;
+
+    fn parse(input: &str) -> Result<Vec<GameCard>, SpanError> {
+        input
+            .lines()
+            .zip(1..)
+            .map(|(line, expected_id)| {
+                let game_card = line.parse::<GameCard>()?;
+                // Part 2 hands out copies by id, so ids must count up from 1
+                if game_card.id == expected_id {
+                    Ok(game_card)
+                } else {
+                    Err(SpanError::at(
+                        line,
+                        format!(
+                            "Expected card {expected_id} but found card {}",
+                            game_card.id
+                        ),
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    fn part_one(game_cards: &Vec<GameCard>) -> Result<String, String> {
+        total_points(game_cards)
+            .map(|points| points.to_string())
+            .ok_or_else(|| "The points are too many to count".to_string())
+    }
+
+    fn part_two(game_cards: &Vec<GameCard>) -> Result<String, String> {
+        total_cards(game_cards)
+            .map(|cards| cards.to_string())
+            .ok_or_else(|| "The cards are too many to count".to_string())
+    }
+
+    fn queries() -> Vec<Query<Vec<GameCard>>> {
+        vec![Query {
+            name: "card",
+            args: "<id>",
+            help: "Show a card, its matches and its score",
+            run: |game_cards, args| {
+                let id = repl::arg::<usize>(args, 0, "id")?;
+                let gc = game_cards
+                    .get(id.wrapping_sub(1))
+                    .ok_or_else(|| format!("There is no card {id}"))?;
+                let numbers = |ns: &[u32]| {
+                    ns.iter()
+                        .map(|n| n.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                let matches = gc.match_count();
+                Ok(format!(
+                    "Card {}: {} | {}\n{matches} match{}, scoring {}",
+                    gc.id,
+                    numbers(&gc.winning_numbers),
+                    numbers(&gc.player_numbers),
+                    if matches == 1 { "" } else { "es" },
+                    gc.score()
+                        .map_or("more than a u32 holds".to_string(), |s| s.to_string())
+                ))
+            },
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{trace::TraceFormat, SolveError};
+
+    use super::*;
+
+    mod part_01_tests {
+        use super::*;
+        #[test]
+        fn a_game_card_with_0_matching_numbers_scores_0() {
+            let game_card = GameCard {
+                id: 0,
+                player_numbers: vec![1, 2, 3, 4, 5],
+                winning_numbers: vec![6, 7, 8, 9, 10],
+            };
+            assert_eq!(game_card.score(), Some(0));
+        }
+
+        #[test]
+        fn a_game_card_with_1_matching_numbers_scores_1() {
+            let game_card = GameCard {
+                id: 0,
+                player_numbers: vec![1, 2, 3, 4, 5],
+                winning_numbers: vec![5, 6, 7, 8, 9],
+            };
+            assert_eq!(game_card.score(), Some(1));
+        }
+
+        #[test]
+        fn a_game_card_with_3_matching_numbers_scores_8() {
+            let game_card = GameCard {
+                id: 0,
+                player_numbers: vec![1, 2, 3, 4, 5],
+                winning_numbers: vec![1, 6, 3, 5, 9],
+            };
+            assert_eq!(game_card.score(), Some(4));
+        }
+
+        #[test]
+        fn a_game_card_string_when_parsed_has_an_id() {
+            let line = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
+            let game_card = line.parse::<GameCard>().unwrap();
+            assert_eq!(game_card.id, 1);
+        }
+
+        #[test]
+        fn a_game_card_string_when_multiple_spaces_before_id_can_be_parsed() {
+            let line = "Card   1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
+            let game_card = line.parse::<GameCard>().unwrap();
+            assert_eq!(game_card.id, 1);
+        }
+
+        #[test]
+        fn a_game_card_string_when_parsed_has_winning_numbers() {
+            let line = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
+            let game_card = line.parse::<GameCard>().unwrap();
+            assert_eq!(game_card.winning_numbers, [41, 48, 83, 86, 17]);
+        }
+
+        #[test]
+        fn a_game_card_string_when_parsed_has_player_numbers() {
+            let line = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
+            let game_card = line.parse::<GameCard>().unwrap();
+            assert_eq!(game_card.player_numbers, [83, 86, 6, 31, 17, 9, 48, 53]);
+        }
+
+        #[test]
+        fn a_game_card_string_with_a_bad_number_cannot_be_parsed() {
+            let line = "Card 1: 41 4x 83 | 83 86";
+            let err = line.parse::<GameCard>().unwrap_err().locate(4, line);
+            assert_eq!((err.column, err.width), (12, 2));
+        }
+
+        #[test]
+        fn game_cards_out_of_order_are_reported_on_their_line() {
+            let input = "Card 1: 1 | 1\nCard 3: 2 | 2";
+            let Err(SolveError::Parse(err)) = aoc_common::solve::<Day04>(input, &[1]) else {
+                panic!("The input should not parse");
+            };
+            assert_eq!(err.line, 2);
+            assert_eq!(err.message, "Expected card 2 but found card 3");
+        }
+    }
+
+    mod part_02_tests {
+        use super::*;
+
+        #[test]
+        fn part_two_traces_the_copies_of_each_card() {
+            let input = "Card 1: 1 2 | 1 2\nCard 2: 3 | 3\nCard 3: 4 | 5";
+            let cards = Day04::parse(input).unwrap();
+            let (total, trace) =
+                aoc_common::trace::capture(TraceFormat::Json, || Day04::part_two(&cards));
+            assert_eq!(total.unwrap(), "7");
+            assert_eq!(
+                trace,
+                [
+                    r#"{"source":"day-04","event":"copies","card":1,"copies":1,"matches":2}"#,
+                    r#"{"source":"day-04","event":"copies","card":2,"copies":2,"matches":1}"#,
+                    r#"{"source":"day-04","event":"copies","card":3,"copies":4,"matches":0}"#,
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn scores_and_copies_too_large_to_count_are_errors() {
+        let numbers = (1..=40)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let input = format!("Card 1: {numbers} | {numbers}\n");
+        let cards = Day04::parse(&input).unwrap();
+        assert_eq!(cards[0].score(), None);
+        assert_eq!(
+            Day04::part_one(&cards),
+            Err("The points are too many to count".to_string())
+        );
+
+        // Every card wins a copy of all the cards after it, doubling them
+        let input = (1..=70)
+            .map(|id| format!("Card {id}: {numbers} | {numbers}\n"))
+            .collect::<String>();
+        let cards = Day04::parse(&input).unwrap();
+        assert_eq!(
+            Day04::part_two(&cards),
+            Err("The cards are too many to count".to_string())
+        );
+    }
+
+    #[test]
+    fn the_card_query_shows_matches_and_score() {
+        let cards = Day04::parse("Card 1: 41 48 83 | 83 86 48\nCard 2: 1 | 2").unwrap();
+        let response = repl::respond::<Day04>(&cards, &Day04::queries(), "card 1");
+        assert_eq!(
+            response,
+            Ok(repl::Response::Text(
+                "Card 1: 41 48 83 | 83 86 48\n2 matches, scoring 2".to_string()
+            ))
+        );
+        assert_eq!(
+            repl::respond::<Day04>(&cards, &Day04::queries(), "card 3"),
+            Err("There is no card 3".to_string())
+        );
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Winning and player numbers for one card, each list without repeats as
+    /// in the puzzle.
+    fn numbers() -> impl Strategy<Value = (Vec<u32>, Vec<u32>)> {
+        (
+            prop::collection::hash_set(1..100_u32, 1..=10),
+            prop::collection::hash_set(1..100_u32, 1..=25),
+        )
+            .prop_map(|(winning, player)| {
+                (winning.into_iter().collect(), player.into_iter().collect())
+            })
+    }
+
+    fn card_line(id: usize, winning: &[u32], player: &[u32]) -> String {
+        let column = |ns: &[u32]| {
+            ns.iter()
+                .map(|n| format!("{n:>2}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        format!("Card {id:>3}: {} | {}", column(winning), column(player))
+    }
+
+    fn cards() -> impl Strategy<Value = Vec<(Vec<u32>, Vec<u32>)>> {
+        prop::collection::vec(numbers(), 1..40)
+    }
+
+    fn input(cards: &[(Vec<u32>, Vec<u32>)]) -> String {
+        cards
+            .iter()
+            .zip(1..)
+            .map(|((winning, player), id)| card_line(id, winning, player))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn matches((winning, player): &(Vec<u32>, Vec<u32>)) -> usize {
+        player.iter().filter(|n| winning.contains(n)).count()
+    }
+
+    proptest! {
+        #[test]
+        fn card_lines_parse_to_their_numbers(id in 1..1000_usize, (winning, player) in numbers()) {
+            let card = card_line(id, &winning, &player).parse::<GameCard>().unwrap();
+            prop_assert_eq!(card.id, id);
+            prop_assert_eq!(card.winning_numbers, winning);
+            prop_assert_eq!(card.player_numbers, player);
+        }
+
+        #[test]
+        fn part_one_doubles_for_every_match_after_the_first(cards in cards()) {
+            let expected = cards
+                .iter()
+                .map(|card| match matches(card) {
+                    0 => 0,
+                    m => 1 << (m - 1),
+                })
+                .sum::<u32>();
+            let parsed = Day04::parse(&input(&cards)).unwrap();
+            prop_assert_eq!(Day04::part_one(&parsed), Ok(expected.to_string()));
+        }
+
+        #[test]
+        fn part_two_counts_every_copy_won(cards in cards()) {
+            // Counted from the last card back: each card is itself plus the
+            // copies won by the cards it wins.
+            let mut won = vec![0_usize; cards.len()];
+            for i in (0..cards.len()).rev() {
+                let last = (i + matches(&cards[i])).min(cards.len() - 1);
+                won[i] = 1 + won[i + 1..=last].iter().sum::<usize>();
+            }
+            let parsed = Day04::parse(&input(&cards)).unwrap();
+            prop_assert_eq!(Day04::part_two(&parsed), Ok(won.iter().sum::<usize>().to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
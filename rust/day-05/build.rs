@@ -0,0 +1,3 @@
+fn main() {
+    aoc_common::generate_example_tests();
+}
@@ -0,0 +1,628 @@
+//! Day 5: If You Give A Seed A Fertilizer. An almanac maps seeds to soil,
+//! soil to fertilizer and so on down to locations; the answer is the lowest
+//! location of any seed, in part 2 over whole ranges of seeds.
+
+#![warn(missing_docs)]
+
+use std::{collections::HashMap, str::FromStr, time::Duration};
+
+use aoc_common::{
+    budget,
+    repl::{self, Query},
+    Solution, SpanError,
+};
+
+/// Part 1: the lowest location any of the almanac's seeds maps to, or `None`
+/// if its maps don't lead from seeds to locations.
+pub fn lowest_location(almanac: &Almanac) -> Option<u32> {
+    almanac
+        .seeds
+        .iter()
+        .filter_map(|&seed| almanac.ag_map.convert("seed", "location", seed))
+        .min()
+}
+
+/// Part 2: the lowest location of any seed when the seeds are read as pairs
+/// of a first seed and a count, or `None` if the maps don't lead from seeds
+/// to locations or no pair has any seeds. A last seed without a count is
+/// ignored.
+pub fn lowest_location_of_ranges(almanac: &Almanac) -> Option<u64> {
+    let seed_ranges = almanac
+        .seeds
+        .chunks_exact(2)
+        .map(|pair| {
+            let start = u64::from(pair[0]);
+            (start, start + u64::from(pair[1]))
+        })
+        .filter(|&(start, end)| start < end)
+        .collect::<Vec<_>>();
+    almanac
+        .ag_map
+        .convert_ranges("seed", "location", seed_ranges)?
+        .into_iter()
+        .map(|(start, _end)| start)
+        .min()
+}
+
+const NO_LOCATIONS: &str = "The almanac does not map seeds to locations";
+
+/// The [`Solution`] for day 5: the input is one [`Almanac`].
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = aoc_common::day_number(env!("CARGO_PKG_NAME"));
+
+    type Parsed = Almanac;
+
+    fn parse(input: &str) -> Result<Almanac, SpanError> {
+        input.parse::<Almanac>()
+    }
+
+    fn part_one(almanac: &Almanac) -> Result<String, String> {
+        lowest_location(almanac)
+            .map(|location| location.to_string())
+            .ok_or_else(|| NO_LOCATIONS.to_string())
+    }
+
+    fn part_two(almanac: &Almanac) -> Result<String, String> {
+        lowest_location_of_ranges(almanac)
+            .map(|location| location.to_string())
+            .ok_or_else(|| NO_LOCATIONS.to_string())
+    }
+
+    fn queries() -> Vec<Query<Almanac>> {
+        vec![Query {
+            name: "convert",
+            args: "<from> <to> <value>",
+            help: "Convert a value from one category to another, such as seed to soil",
+            run: |almanac, args| {
+                let from = repl::arg::<String>(args, 0, "from")?;
+                let to = repl::arg::<String>(args, 1, "to")?;
+                let value = repl::arg::<u32>(args, 2, "value")?;
+                // Mappings that go round in a cycle never reach `to`
+                budget::limit(Some(Duration::from_secs(1)), || {
+                    almanac.ag_map.convert(&from, &to, value)
+                })
+                .map_err(|_| format!("{from} never reaches {to}: the mappings form a cycle"))?
+                .map(|converted| format!("{from} {value} is {to} {converted}"))
+                .ok_or_else(|| format!("There is no way from {from} to {to}"))
+            },
+        }]
+    }
+}
+
+/// One line of a map: `range_length` values from `source_range_start` on
+/// map to as many from `dest_range_start` on.
+#[derive(Debug, Clone, Copy)]
+pub struct MappingRange {
+    source_range_start: u32,
+    dest_range_start: u32,
+    range_length: u32,
+}
+
+impl MappingRange {
+    /// A range mapping `range_length` values from `source_range_start` on.
+    /// Both ranges should end within `u32`, as parsing checks.
+    pub fn new(source_range_start: u32, dest_range_start: u32, range_length: u32) -> MappingRange {
+        MappingRange {
+            source_range_start,
+            dest_range_start,
+            range_length,
+        }
+    }
+
+    /// Whether the range maps `source`.
+    pub fn source_in_range(&self, source: u32) -> bool {
+        self.source_range_start <= source && source - self.source_range_start < self.range_length
+    }
+
+    /// Where the range maps `source`, which must be in the range.
+    pub fn calc_dest(&self, source: u32) -> u32 {
+        let delta = source - self.source_range_start;
+        self.dest_range_start + delta
+    }
+
+    fn source_end(&self) -> u64 {
+        u64::from(self.source_range_start) + u64::from(self.range_length)
+    }
+
+    fn dest_end(&self) -> u64 {
+        u64::from(self.dest_range_start) + u64::from(self.range_length)
+    }
+}
+
+/// One map of the almanac, such as `seed-to-soil`. Values outside all of its
+/// ranges map to themselves.
+#[derive(Debug, Clone, Default)]
+pub struct Mapping {
+    ranges: Vec<MappingRange>,
+    source_category: String,
+    dest_category: String,
+}
+
+impl Mapping {
+    /// A map from `source_category` to `dest_category` with no ranges yet.
+    pub fn new(source_category: &str, dest_category: &str) -> Self {
+        Mapping {
+            source_category: source_category.to_string(),
+            dest_category: dest_category.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// The map with `mapping_range` added.
+    pub fn with_range(mut self, mapping_range: MappingRange) -> Self {
+        self.ranges.push(mapping_range);
+        self
+    }
+
+    /// Where the map sends `source`.
+    pub fn convert(&self, source: u32) -> u32 {
+        self.ranges
+            .iter()
+            .find(|r| r.source_in_range(source))
+            .map(|r| r.calc_dest(source))
+            .unwrap_or(source)
+    }
+
+    /// Converts the half-open range `start..end`, splitting it wherever it
+    /// straddles the edge of a mapping range. An empty range converts to
+    /// nothing.
+    pub fn convert_range(&self, start: u64, end: u64) -> Vec<(u64, u64)> {
+        if end <= start {
+            return Vec::new();
+        }
+        let mut converted = Vec::new();
+        let mut unmapped = vec![(start, end)];
+        for range in self.ranges.iter() {
+            let src_start = u64::from(range.source_range_start);
+            let src_end = range.source_end();
+            let offset = i64::from(range.dest_range_start) - i64::from(range.source_range_start);
+            let mut remaining = Vec::new();
+            for (s, e) in unmapped {
+                let overlap_start = s.max(src_start);
+                let overlap_end = e.min(src_end);
+                if overlap_start < overlap_end {
+                    converted.push((
+                        overlap_start.saturating_add_signed(offset),
+                        overlap_end.saturating_add_signed(offset),
+                    ));
+                    if s < overlap_start {
+                        remaining.push((s, overlap_start));
+                    }
+                    if overlap_end < e {
+                        remaining.push((overlap_end, e));
+                    }
+                } else {
+                    remaining.push((s, e));
+                }
+            }
+            unmapped = remaining;
+        }
+        converted.extend(unmapped);
+        converted
+    }
+}
+
+/// Every map of the almanac, keyed by the category each maps from.
+#[derive(Default, Clone)]
+pub struct AgMap {
+    mappings: HashMap<String, Mapping>,
+}
+
+impl AgMap {
+    /// How many maps there are.
+    pub fn mappings_len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    /// The maps with `mapping` added, replacing any from the same category.
+    pub fn with_mapping(mut self, mapping: Mapping) -> AgMap {
+        self.mappings
+            .insert(mapping.source_category.clone(), mapping);
+        self
+    }
+
+    /// Follows the maps from `source_category` until `dest_category`, or
+    /// returns `None` if they lead nowhere near it. Maps that go round in a
+    /// cycle without reaching `dest_category` never return, unless stopped by
+    /// a [`budget`].
+    pub fn convert(&self, source_category: &str, dest_category: &str, input: u32) -> Option<u32> {
+        let mut done = false;
+        let mut curr_category = source_category.to_string();
+        let mut curr_input = input;
+        while !done {
+            if curr_category != dest_category {
+                aoc_common::budget::check(|| {
+                    format!("converting {input} from {source_category}, at {curr_category}")
+                });
+                let curr_mapping = self.mappings.get(&curr_category)?;
+                let converted = curr_mapping.convert(curr_input);
+                aoc_common::trace!(
+                    "convert",
+                    from = curr_category,
+                    to = curr_mapping.dest_category,
+                    value = curr_input,
+                    result = converted
+                );
+                curr_input = converted;
+                curr_category = curr_mapping.dest_category.clone();
+            } else {
+                done = true;
+            }
+        }
+        Some(curr_input)
+    }
+
+    /// Like [`AgMap::convert`] for half-open ranges of values, which come
+    /// out split wherever a map treats parts of them differently.
+    pub fn convert_ranges(
+        &self,
+        source_category: &str,
+        dest_category: &str,
+        ranges: Vec<(u64, u64)>,
+    ) -> Option<Vec<(u64, u64)>> {
+        let mut curr_category = source_category;
+        let mut curr_ranges = ranges;
+        while curr_category != dest_category {
+            aoc_common::budget::check(|| {
+                format!("converting ranges from {source_category}, at {curr_category}")
+            });
+            let curr_mapping = self.mappings.get(curr_category)?;
+            curr_ranges = curr_ranges
+                .into_iter()
+                .flat_map(|(start, end)| curr_mapping.convert_range(start, end))
+                .collect();
+            curr_category = &curr_mapping.dest_category;
+        }
+        Some(curr_ranges)
+    }
+}
+
+/// A whole almanac: the seeds and the maps.
+pub struct Almanac {
+    /// The numbers after `seeds:`.
+    pub seeds: Vec<u32>,
+    /// The maps.
+    pub ag_map: AgMap,
+}
+
+fn parse_nums(s: &str) -> Result<Vec<u32>, SpanError> {
+    s.split_whitespace()
+        .map(|n| {
+            n.parse::<u32>()
+                .map_err(|err| SpanError::at(n, format!("Cannot parse `{n}` as a number: {err}")))
+        })
+        .collect()
+}
+
+impl FromStr for Mapping {
+    type Err = SpanError;
+
+    fn from_str(block: &str) -> Result<Mapping, <Mapping as FromStr>::Err> {
+        let mut lines = block.lines();
+        let header = lines
+            .next()
+            .ok_or_else(|| SpanError::at(block, "Empty mapping block"))?;
+        let (source, dest) = header
+            .strip_suffix(" map:")
+            .and_then(|s| s.split_once("-to-"))
+            .ok_or_else(|| SpanError::at(header, "Malformed mapping header"))?;
+        let mut mapping = Mapping::new(source, dest);
+        for line in lines {
+            match parse_nums(line)?[..] {
+                [dest_start, source_start, length] => {
+                    let range = MappingRange::new(source_start, dest_start, length);
+                    // Converted values are u32s too, so both ends must fit
+                    if range.source_end().max(range.dest_end()) > 1 << 32 {
+                        return Err(SpanError::at(
+                            line,
+                            format!("Mapping range runs past {}", u32::MAX),
+                        ));
+                    }
+                    mapping = mapping.with_range(range);
+                }
+                _ => return Err(SpanError::at(line, "Mapping range needs three numbers")),
+            }
+        }
+        Ok(mapping)
+    }
+}
+
+impl FromStr for Almanac {
+    type Err = SpanError;
+
+    fn from_str(input: &str) -> Result<Almanac, <Almanac as FromStr>::Err> {
+        let mut blocks = input.split("\n\n");
+        let first = blocks.next().unwrap_or(input).trim();
+        let seeds = first
+            .strip_prefix("seeds:")
+            .ok_or_else(|| SpanError::at(first, "Almanac must start with `seeds:`"))?;
+        let seeds = parse_nums(seeds)?;
+        if seeds.is_empty() {
+            return Err(SpanError::at(first, "Almanac lists no seeds"));
+        }
+        let ag_map = blocks
+            .map(|block| block.trim().parse::<Mapping>())
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .fold(AgMap::default(), AgMap::with_mapping);
+        Ok(Almanac { seeds, ag_map })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{trace::TraceFormat, SolveError};
+
+    use super::*;
+
+    mod with_a_valid_mapping_range {
+        use super::*;
+
+        fn test_mapping_range() -> MappingRange {
+            MappingRange::new(52, 98, 4)
+        }
+
+        #[test]
+        fn when_input_is_in_source_range_in_range_is_true() {
+            let seed_number = 55;
+            let seed_to_soil_map = test_mapping_range();
+            assert!(seed_to_soil_map.source_in_range(seed_number));
+        }
+
+        #[test]
+        fn when_input_is_smaller_than_source_range_start_in_range_is_false() {
+            let seed_number = 51;
+            let seed_to_soil_map = test_mapping_range();
+            assert!(!seed_to_soil_map.source_in_range(seed_number));
+        }
+
+        #[test]
+        fn when_input_is_larger_than_source_range_start_in_range_is_false() {
+            let seed_number = 56;
+            let seed_to_soil_map = test_mapping_range();
+            assert!(!seed_to_soil_map.source_in_range(seed_number));
+        }
+
+        #[test]
+        fn when_given_a_source_num_it_can_calculate_a_destination_num() {
+            let seed_number = 53;
+            let seed_to_soil_map = test_mapping_range();
+            assert_eq!(seed_to_soil_map.calc_dest(seed_number), 99)
+        }
+    }
+
+    mod with_a_valid_mapping {
+        use super::*;
+
+        #[test]
+        fn when_source_is_in_a_range_the_range_converts_it() {
+            let mapping = Mapping::default()
+                .with_range(MappingRange::new(50, 98, 2))
+                .with_range(MappingRange::new(52, 50, 48));
+            let source = 55;
+            assert_eq!(mapping.convert(source), 53);
+        }
+
+        #[test]
+        fn when_source_is_not_in_a_range_it_is_converted_to_same_dest_number() {
+            let mapping = Mapping::default()
+                .with_range(MappingRange::new(50, 98, 2))
+                .with_range(MappingRange::new(52, 50, 48));
+            let source = 10;
+            assert_eq!(mapping.convert(source), 10);
+        }
+
+        #[test]
+        fn when_quried_returns_its_source_category() {
+            let mapping = Mapping::new("seed", "");
+            assert_eq!(mapping.source_category, "seed")
+        }
+
+        #[test]
+        fn when_quried_returns_its_destination_category() {
+            let mapping = Mapping::new("", "fertilizer");
+            assert_eq!(mapping.dest_category, "fertilizer")
+        }
+    }
+
+    #[test]
+    fn when_an_ag_map_is_created_it_has_no_mappings() {
+        let ag_map = AgMap::default();
+        assert_eq!(ag_map.mappings_len(), 0)
+    }
+
+    #[test]
+    fn an_ag_map_can_have_mappings_added() {
+        let ag_map = AgMap::default().with_mapping(Mapping::default());
+        assert_eq!(ag_map.mappings_len(), 1);
+    }
+
+    #[test]
+    #[allow(unused_variables)]
+    fn an_ag_map_with_valid_mappings_can_convert_source_input_to_dest_output() {
+        let seed_to_soil = Mapping::new("seed", "soil")
+            .with_range(MappingRange::new(50, 98, 2))
+            .with_range(MappingRange::new(52, 50, 48));
+        let soil_to_fertilizer = Mapping::new("soil", "fertilizer")
+            .with_range(MappingRange::new(0, 15, 37))
+            .with_range(MappingRange::new(37, 52, 2))
+            .with_range(MappingRange::new(39, 0, 15));
+        let ag_map = AgMap::default().with_mapping(seed_to_soil);
+        // .with_mapping(soil_to_fertilizer);
+        assert_eq!(ag_map.convert("seed", "soil", 14), Some(14));
+    }
+
+    const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn an_almanac_can_be_parsed_from_the_example() {
+        let almanac = EXAMPLE.parse::<Almanac>().unwrap();
+        assert_eq!(almanac.seeds, [79, 14, 55, 13]);
+        assert_eq!(almanac.ag_map.mappings_len(), 7);
+    }
+
+    #[test]
+    fn the_example_seeds_convert_to_the_example_soils() {
+        let almanac = EXAMPLE.parse::<Almanac>().unwrap();
+        let soils = almanac
+            .seeds
+            .iter()
+            .map(|&s| almanac.ag_map.convert("seed", "soil", s))
+            .collect::<Vec<_>>();
+        assert_eq!(soils, [Some(81), Some(14), Some(57), Some(13)]);
+    }
+
+    #[test]
+    fn converting_a_seed_traces_every_category_hop() {
+        let almanac = EXAMPLE.parse::<Almanac>().unwrap();
+        let (location, trace) = aoc_common::trace::capture(TraceFormat::Text, || {
+            almanac.ag_map.convert("seed", "location", 79)
+        });
+        assert_eq!(location, Some(82));
+        assert_eq!(trace.len(), 7);
+        assert_eq!(
+            trace[0],
+            "[day-05] convert from=seed to=soil value=79 result=81"
+        );
+        assert!(trace[6].ends_with("to=location value=78 result=82"));
+    }
+
+    #[test]
+    fn a_mapping_range_with_two_numbers_is_reported_on_its_line() {
+        let input = "seeds: 1\n\nseed-to-soil map:\n50 98 2\n52 50\n";
+        let Err(SolveError::Parse(err)) = aoc_common::solve::<Day05>(input, &[1]) else {
+            panic!("The input should not parse");
+        };
+        assert_eq!((err.line, err.column), (5, 1));
+        assert_eq!(err.message, "Mapping range needs three numbers");
+    }
+
+    #[test]
+    fn an_almanac_without_seeds_cannot_be_parsed() {
+        assert!("seed-to-soil map:\n50 98 2".parse::<Almanac>().is_err());
+    }
+
+    #[test]
+    fn mappings_that_form_a_cycle_time_out() {
+        let input = "seeds: 1\n\nseed-to-soil map:\n1 1 1\n\nsoil-to-seed map:\n1 1 1\n";
+        let almanac = Day05::parse(input).unwrap();
+        let timeout = budget::limit(Some(Duration::from_millis(20)), || {
+            almanac.ag_map.convert("seed", "location", 1)
+        })
+        .unwrap_err();
+        assert!(timeout.progress.starts_with("converting 1 from seed, at "));
+    }
+
+    #[test]
+    fn the_convert_query_follows_the_mappings() {
+        let almanac = Day05::parse(EXAMPLE).unwrap();
+        let queries = Day05::queries();
+        assert_eq!(
+            repl::respond::<Day05>(&almanac, &queries, "convert seed soil 79"),
+            Ok(repl::Response::Text("seed 79 is soil 81".to_string()))
+        );
+        assert_eq!(
+            repl::respond::<Day05>(&almanac, &queries, "convert seed location 79"),
+            Ok(repl::Response::Text("seed 79 is location 82".to_string()))
+        );
+        assert_eq!(
+            repl::respond::<Day05>(&almanac, &queries, "convert seed pumpkin 79"),
+            Err("There is no way from seed to pumpkin".to_string())
+        );
+    }
+
+    #[test]
+    fn a_mapping_range_past_the_largest_value_is_reported() {
+        let input = "seeds: 1\n\nseed-to-location map:\n0 0 1\n4294967295 0 2\n";
+        let Err(SolveError::Parse(err)) = aoc_common::solve::<Day05>(input, &[1]) else {
+            panic!("The input should not parse");
+        };
+        assert_eq!((err.line, err.column), (5, 1));
+        assert_eq!(err.message, "Mapping range runs past 4294967295");
+
+        let input = "seeds: 1\n\nseed-to-location map:\n4294967295 0 1\n";
+        let almanac = Day05::parse(input).unwrap();
+        assert_eq!(
+            almanac.ag_map.convert("seed", "location", 0),
+            Some(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn part_01_finds_the_lowest_location_in_the_example() {
+        let almanac = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part_one(&almanac).unwrap(), "35");
+    }
+
+    #[test]
+    fn part_02_finds_the_lowest_location_over_seed_ranges_in_the_example() {
+        let almanac = Day05::parse(EXAMPLE).unwrap();
+        assert_eq!(Day05::part_two(&almanac).unwrap(), "46");
+    }
+
+    #[test]
+    fn a_seed_without_a_count_still_has_a_lowest_location() {
+        let input = "seeds: 79\n\nseed-to-location map:\n0 50 100\n";
+        let answers = aoc_common::solve::<Day05>(input, &[1]).unwrap();
+        assert_eq!(answers.parts[0].answer, "29");
+    }
+
+    #[test]
+    fn a_seed_range_with_no_seeds_has_no_location() {
+        let input = "seeds: 5 0 100 1\n\nseed-to-location map:\n0 50 10\n";
+        let almanac = Day05::parse(input).unwrap();
+        assert_eq!(Day05::part_two(&almanac).unwrap(), "100");
+        assert!(almanac.ag_map.mappings["seed"]
+            .convert_range(5, 5)
+            .is_empty());
+    }
+
+    #[test]
+    fn an_almanac_without_a_way_to_locations_has_no_answer() {
+        let input = "seeds: 1 1\n\nseed-to-soil map:\n0 0 1\n";
+        let almanac = Day05::parse(input).unwrap();
+        let err = Err(NO_LOCATIONS.to_string());
+        assert_eq!(Day05::part_one(&almanac), err);
+        assert_eq!(Day05::part_two(&almanac), err);
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
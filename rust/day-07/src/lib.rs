@@ -0,0 +1,748 @@
+//! Day 7: Camel Cards. Hands of five cards are ranked by type, then card by
+//! card, and each wins its bid times its rank; in part 2, jacks are jokers
+//! that stand in for whatever card makes the best hand.
+
+#![warn(missing_docs)]
+
+use std::{fmt, str::FromStr};
+
+use aoc_common::{
+    repl::{self, Query},
+    Alternate, Solution, SpanError,
+};
+
+/// Part 1: every bid times its hand's rank, the weakest hand ranking 1, or
+/// `None` if that overflows.
+pub fn total_winnings(bets: &[Bet]) -> Option<u64> {
+    let mut bets = bets.iter().collect::<Vec<_>>();
+    bets.sort_by(|b1, b2| b1.hand.cmp(&b2.hand));
+    ranked_winnings(bets.into_iter().map(|bet| bet.bid))
+}
+
+/// Part 2: like [`total_winnings`], with hands ranked by
+/// [`Hand::cmp_jokers_wild`].
+pub fn total_winnings_jokers_wild(bets: &[Bet]) -> Option<u64> {
+    let mut bets = bets.iter().collect::<Vec<_>>();
+    bets.sort_by(|b1, b2| b1.hand.cmp_jokers_wild(&b2.hand));
+    ranked_winnings(bets.into_iter().map(|bet| bet.bid))
+}
+
+/// The sum of each bid times its rank, for bids from the weakest hand up.
+fn ranked_winnings(bids: impl Iterator<Item = u64>) -> Option<u64> {
+    bids.zip(1..).try_fold(0_u64, |sum, (bid, rank)| {
+        sum.checked_add(bid.checked_mul(rank)?)
+    })
+}
+
+const TOO_LARGE: &str = "The winnings are too large to count";
+
+/// The [`Solution`] for day 7: the input is one [`Bet`] per line.
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = aoc_common::day_number(env!("CARGO_PKG_NAME"));
+
+    type Parsed = Vec<Bet>;
+
+    fn parse(input: &str) -> Result<Vec<Bet>, SpanError> {
+        input.lines().map(str::parse::<Bet>).collect()
+    }
+
+    fn part_one(bets: &Vec<Bet>) -> Result<String, String> {
+        total_winnings(bets)
+            .map(|winnings| winnings.to_string())
+            .ok_or_else(|| TOO_LARGE.to_string())
+    }
+
+    fn part_two(bets: &Vec<Bet>) -> Result<String, String> {
+        total_winnings_jokers_wild(bets)
+            .map(|winnings| winnings.to_string())
+            .ok_or_else(|| TOO_LARGE.to_string())
+    }
+
+    fn alternates() -> Vec<Alternate<Vec<Bet>>> {
+        vec![Alternate {
+            name: "brute_force_jokers",
+            part: 2,
+            // Winnings that overflow are reported by the main implementation
+            solve: |bets| jokers_wild_winnings_brute_force(bets).map(|w| w.to_string()),
+        }]
+    }
+
+    fn queries() -> Vec<Query<Vec<Bet>>> {
+        vec![Query {
+            name: "hand",
+            args: "<n>",
+            help: "Show the nth hand, its score and its score with jokers wild",
+            run: |bets, args| {
+                let n = repl::arg::<usize>(args, 0, "n")?;
+                let bet = bets
+                    .get(n.wrapping_sub(1))
+                    .ok_or_else(|| format!("There is no hand {n}; there are {}", bets.len()))?;
+                let jokers_wild = bet.hand.jokers_wild_score();
+                Ok(format!(
+                    "{} bid {}: {:?}, {jokers_wild:?} with jokers wild",
+                    bet.hand,
+                    bet.bid,
+                    bet.hand.score()
+                ))
+            },
+        }]
+    }
+}
+
+/// The best score a hand can make, found by letting each joker stand for
+/// every card in turn.
+fn brute_force_jokers_wild_score(hand: &Hand) -> Score {
+    let others = hand
+        .cards
+        .iter()
+        .copied()
+        .filter(|&c| c != Card::Jack)
+        .collect::<Vec<_>>();
+    let jokers = (hand.cards.len() - others.len()) as u32;
+    (0..all_cards().len().pow(jokers))
+        .map(|mut choice| {
+            let mut cards = others.clone();
+            for _ in 0..jokers {
+                cards.push(all_cards()[choice % all_cards().len()]);
+                choice /= all_cards().len();
+            }
+            let cards = cards.try_into().expect("A hand keeps 5 cards");
+            Hand { cards }.score()
+        })
+        .max()
+        .expect("There is always at least one choice")
+}
+
+/// Part 2 without `get_jokers_wild_best_counts` or `sort_jokers_wild`.
+fn jokers_wild_winnings_brute_force(bets: &[Bet]) -> Option<u64> {
+    // `None` sorts before any card, as a joker should.
+    let strengths = |hand: &Hand| hand.cards.map(|c| Some(c).filter(|&c| c != Card::Jack));
+    let mut ranked = bets
+        .iter()
+        .map(|bet| {
+            let key = (
+                brute_force_jokers_wild_score(&bet.hand),
+                strengths(&bet.hand),
+            );
+            (key, bet.bid)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
+    ranked_winnings(ranked.into_iter().map(|(_, bid)| bid))
+}
+
+fn score_counts(counts: &[usize]) -> Score {
+    let n_of_a_kind = |n: usize| counts.iter().filter(|count| **count == n).count();
+
+    if n_of_a_kind(5) == 1 {
+        Score::FiveOfAKind
+    } else if n_of_a_kind(4) == 1 {
+        Score::FourOfAKind
+    } else if n_of_a_kind(3) == 1 && n_of_a_kind(2) == 1 {
+        Score::FullHouse
+    } else if n_of_a_kind(3) == 1 {
+        Score::ThreeOfAKind
+    } else if n_of_a_kind(2) == 2 {
+        Score::TwoPair
+    } else if n_of_a_kind(2) == 1 {
+        Score::Pair
+    } else {
+        Score::HighCard
+    }
+}
+
+fn get_jokers_wild_best_counts(hand: &Hand) -> [usize; 13] {
+    let mut counts = hand.get_counts();
+    let joker_index = all_cards()
+        .into_iter()
+        .position(|c| c == Card::Jack)
+        .expect("All valid cards are in all_cards");
+    let joker_count = counts[joker_index];
+    counts[joker_index] -= joker_count;
+    let high_count = counts.iter().max().unwrap_or(&0);
+    let high_count_position = counts
+        .iter()
+        .position(|count| count == high_count)
+        .expect("Some positon must have high count");
+
+    // We will always get the best hand by adding the jokers to the
+    // position that has "most of a kind"
+    counts[high_count_position] += joker_count;
+    counts
+}
+
+fn sort_jokers_wild(h1: &Hand, h2: &Hand) -> std::cmp::Ordering {
+    let h1_best = get_jokers_wild_best_counts(h1);
+    let h2_best = get_jokers_wild_best_counts(h2);
+    let h1_score = score_counts(&h1_best);
+    let h2_score = score_counts(&h2_best);
+    if h1_score != h2_score {
+        return h1_score.cmp(&h2_score);
+    }
+
+    // If same Score type compare by cards
+    h1.cards
+        .iter()
+        .zip(h2.cards.iter())
+        .find(|(c1, c2)| c1 != c2)
+        .map_or(std::cmp::Ordering::Equal, |(c1, c2)| match (c1, c2) {
+            // Jacks (which are Jokers in this scheme) are always
+            // sorted as less-than non-Jacks.
+            (&Card::Jack, _) => std::cmp::Ordering::Less,
+            (_, &Card::Jack) => std::cmp::Ordering::Greater,
+            (c1, c2) => c1.cmp(c2),
+        })
+}
+
+/// A card, ordered from weakest to strongest. Parsed from `2` to `9`, `T`,
+/// `J`, `Q`, `K` and `A`.
+#[derive(Eq, PartialEq, Debug, Copy, Clone, PartialOrd, Ord)]
+pub enum Card {
+    /// `2` to `9`.
+    Number(u64),
+    /// `T`.
+    Ten,
+    /// `J`, a joker in part 2.
+    Jack,
+    /// `Q`.
+    Queen,
+    /// `K`.
+    King,
+    /// `A`.
+    Ace,
+}
+
+fn all_cards() -> [Card; 13] {
+    [
+        Card::Number(2),
+        Card::Number(3),
+        Card::Number(4),
+        Card::Number(5),
+        Card::Number(6),
+        Card::Number(7),
+        Card::Number(8),
+        Card::Number(9),
+        Card::Ten,
+        Card::Jack,
+        Card::Queen,
+        Card::King,
+        Card::Ace,
+    ]
+}
+
+impl From<Card> for char {
+    fn from(card: Card) -> char {
+        match card {
+            Card::Number(rank) => char::from_digit(rank as u32, 10).expect("Ranks are digits"),
+            Card::Ten => 'T',
+            Card::Jack => 'J',
+            Card::Queen => 'Q',
+            Card::King => 'K',
+            Card::Ace => 'A',
+        }
+    }
+}
+
+impl TryFrom<char> for Card {
+    type Error = String;
+
+    fn try_from(n: char) -> Result<Card, <Card as TryFrom<char>>::Error> {
+        match n {
+            n if ('2'..='9').contains(&n) => {
+                let rank = n.to_digit(10).expect("Checked that `n` is a digit");
+                Ok(Card::Number(rank.into()))
+            }
+            'T' => Ok(Card::Ten),
+            'J' => Ok(Card::Jack),
+            'Q' => Ok(Card::Queen),
+            'K' => Ok(Card::King),
+            'A' => Ok(Card::Ace),
+            other => Err(format!("`{other}` is not a valid card rank")),
+        }
+    }
+}
+
+/// The type of a hand, ordered from weakest to strongest.
+#[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Score {
+    /// Five different cards.
+    HighCard,
+    /// Two of one card.
+    Pair,
+    /// Two of one card and two of another.
+    TwoPair,
+    /// Three of one card.
+    ThreeOfAKind,
+    /// Three of one card and two of another.
+    FullHouse,
+    /// Four of one card.
+    FourOfAKind,
+    /// Five of one card.
+    FiveOfAKind,
+}
+
+/// Five cards, such as `KTJJT`. Hands order by [`Score`], then card by card
+/// from the first.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Hand {
+    /// The cards in the order they were dealt.
+    pub cards: [Card; 5],
+}
+
+impl Hand {
+    fn get_counts(&self) -> [usize; 13] {
+        let mut counts = [0_usize; 13];
+        for card in self.cards.iter() {
+            let index = all_cards()
+                .iter()
+                .position(|c| c == card)
+                .expect("All valid cands are in array");
+            counts[index] += 1;
+        }
+        counts
+    }
+
+    /// The hand's type.
+    pub fn score(&self) -> Score {
+        let counts = self.get_counts();
+        score_counts(&counts)
+    }
+
+    /// The best type the hand can make with its jacks as jokers.
+    pub fn jokers_wild_score(&self) -> Score {
+        score_counts(&get_jokers_wild_best_counts(self))
+    }
+
+    /// Orders hands as part 2 does: by [`Hand::jokers_wild_score`], then card
+    /// by card with jokers weaker than any other card.
+    pub fn cmp_jokers_wild(&self, other: &Hand) -> std::cmp::Ordering {
+        sort_jokers_wild(self, other)
+    }
+}
+
+impl FromStr for Hand {
+    type Err = SpanError;
+
+    fn from_str(cards_str: &str) -> Result<Hand, <Hand as std::str::FromStr>::Err> {
+        let mut cards = [Card::Ace; 5];
+        let mut card_count = 0;
+        for (i, c) in cards_str.char_indices() {
+            let card_str = &cards_str[i..i + c.len_utf8()];
+            let slot = cards
+                .get_mut(card_count)
+                .ok_or_else(|| SpanError::at(card_str, "A hand has only 5 cards"))?;
+            *slot = Card::try_from(c).map_err(|err| SpanError::at(card_str, err))?;
+            card_count += 1;
+        }
+        if card_count < cards.len() {
+            return Err(SpanError::at(
+                cards_str,
+                format!("A hand needs 5 cards but has {card_count}"),
+            ));
+        }
+        Ok(Hand { cards })
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.cards
+            .iter()
+            .try_for_each(|&card| write!(f, "{}", char::from(card)))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Hand) -> std::cmp::Ordering {
+        let s1 = self.score();
+        let s2 = other.score();
+        if s1 != s2 {
+            return s1.cmp(&s2);
+        }
+        // If same Score type compare by cards
+        self.cards
+            .iter()
+            .zip(other.cards.iter())
+            .find(|(c1, c2)| c1 != c2)
+            .map_or(std::cmp::Ordering::Equal, |(c1, c2)| c1.cmp(c2))
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Hand) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A line of the input: a hand and what was bid on it.
+#[derive(Debug)]
+pub struct Bet {
+    /// The hand.
+    pub hand: Hand,
+    /// The bid.
+    pub bid: u64,
+}
+
+impl FromStr for Bet {
+    type Err = SpanError;
+
+    fn from_str(bet_str: &str) -> Result<Bet, <Bet as std::str::FromStr>::Err> {
+        let (hand_str, bid_str) = bet_str
+            .split_once(' ')
+            .ok_or_else(|| SpanError::at(bet_str, "No space in bet string"))?;
+        let hand = hand_str.parse::<Hand>()?;
+        let bid = bid_str
+            .parse::<u64>()
+            .map_err(|err| SpanError::at(bid_str, format!("Cannot parse bid: {err}")))?;
+        Ok(Bet { hand, bid })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use aoc_common::SolveError;
+
+    use super::*;
+
+    #[test]
+    fn char_0_cannot_be_parsed_into_a_card() {
+        assert_eq!(
+            Card::try_from('0'),
+            Err("`0` is not a valid card rank".into())
+        )
+    }
+
+    #[test]
+    fn char_1_cannot_be_parsed_into_a_card() {
+        assert_eq!(
+            Card::try_from('1'),
+            Err("`1` is not a valid card rank".into())
+        )
+    }
+
+    #[test]
+    fn all_valid_num_chars_can_be_parsed_to_number_cards() -> Result<(), String> {
+        let cards = "23456789TJQKA"
+            .chars()
+            .map(Card::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            cards,
+            [
+                Card::Number(2),
+                Card::Number(3),
+                Card::Number(4),
+                Card::Number(5),
+                Card::Number(6),
+                Card::Number(7),
+                Card::Number(8),
+                Card::Number(9),
+                Card::Ten,
+                Card::Jack,
+                Card::Queen,
+                Card::King,
+                Card::Ace,
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn all_cards_have_proper_ordering() -> Result<(), String> {
+        let cards = "23456789TJQKA"
+            .chars()
+            .map(Card::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        assert!(cards.windows(2).all(|pair| pair[0] < pair[1]));
+        Ok(())
+    }
+
+    #[test]
+    fn a_string_of_cards_can_be_parsed_into_a_hand() {
+        let cards = "246KA";
+        let hand = Hand::from_str(cards).unwrap();
+        assert_eq!(
+            hand.cards,
+            [
+                Card::Number(2),
+                Card::Number(4),
+                Card::Number(6),
+                Card::King,
+                Card::Ace,
+            ]
+        )
+    }
+
+    fn assert_hand_score(hand: &str, expected_score: &Score) {
+        let hand = hand.parse::<Hand>().unwrap();
+        assert_eq!(&hand.score(), expected_score);
+    }
+
+    #[test]
+    fn a_hand_with_a_high_card_only_can_be_scored() {
+        assert_hand_score("246KA", &Score::HighCard);
+    }
+
+    #[test]
+    fn a_hand_with_a_single_pair_can_be_scored() {
+        assert_hand_score("242TK", &Score::Pair);
+    }
+
+    #[test]
+    fn a_hand_with_two_pair_can_be_scored() {
+        assert_hand_score("22AKA", &Score::TwoPair);
+    }
+
+    #[test]
+    fn a_hand_with_three_of_a_kind_can_be_scored() {
+        assert_hand_score("4454Q", &Score::ThreeOfAKind);
+    }
+
+    #[test]
+    fn a_hand_with_a_full_house_can_be_scored() {
+        assert_hand_score("777JJ", &Score::FullHouse);
+    }
+
+    #[test]
+    fn a_hand_with_four_of_a_kind_can_be_scored() {
+        assert_hand_score("KK2KK", &Score::FourOfAKind);
+    }
+
+    #[test]
+    fn a_hand_with_five_of_a_kind_can_be_scored() {
+        assert_hand_score("AAAAA", &Score::FiveOfAKind);
+    }
+
+    #[test]
+    fn if_hands_scores_are_different_they_are_ordered_by_score() {
+        let hands = [
+            Hand::from_str("234TJ").unwrap(), // HighCard
+            Hand::from_str("42TTJ").unwrap(), // Pair
+            Hand::from_str("334T4").unwrap(), // TwoPair
+            Hand::from_str("232T2").unwrap(), // ThreeOfAKind
+            Hand::from_str("24422").unwrap(), // FullHouse
+            Hand::from_str("JJJTJ").unwrap(), // FourOfAKind
+            Hand::from_str("AAAAA").unwrap(), // FiveOfkind
+        ];
+        hands
+            .windows(2)
+            .for_each(|h| assert_eq!(h[0].partial_cmp(&h[1]), Some(Ordering::Less)));
+    }
+
+    #[test]
+    fn if_hands_scores_are_equal_they_are_ordered_by_first_card() {
+        let h1 = Hand::from_str("23456").unwrap();
+        let h2 = Hand::from_str("32456").unwrap();
+        assert_eq!(h1.partial_cmp(&h2), Some(Ordering::Less));
+    }
+
+    #[test]
+    fn if_hands_scores_are_equal_hands_are_ordered_by_first_differing_card() {
+        let h1 = Hand::from_str("22645").unwrap();
+        let h2 = Hand::from_str("22546").unwrap();
+        assert_eq!(h1.partial_cmp(&h2), Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn if_hands_have_all_same_cards_they_are_equal() {
+        let h1 = Hand::from_str("23456").unwrap();
+        let h2 = Hand::from_str("23456").unwrap();
+        assert_eq!(h1.partial_cmp(&h2), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn a_bet_has_a_hand() {
+        let bet_str = "32T3K 765";
+        let bet = Bet::from_str(bet_str).unwrap();
+        assert_eq!(bet.hand, Hand::from_str("32T3K").unwrap());
+    }
+
+    #[test]
+    fn a_bet_has_a_bid() {
+        let bet_str = "32T3K 765";
+        let bet = Bet::from_str(bet_str).unwrap();
+        assert_eq!(bet.bid, 765);
+    }
+
+    #[test]
+    fn a_hand_with_too_many_cards_cannot_be_parsed() {
+        let cards = "23456A";
+        let err = Hand::from_str(cards).unwrap_err().locate(7, cards);
+        assert_eq!(err.column, 6);
+        assert_eq!(err.message, "A hand has only 5 cards");
+    }
+
+    #[test]
+    fn a_hand_with_too_few_cards_cannot_be_parsed() {
+        let err = Hand::from_str("2345").unwrap_err();
+        assert_eq!(err.message, "A hand needs 5 cards but has 4");
+    }
+
+    #[test]
+    fn an_invalid_card_is_reported_where_it_appears() {
+        let input = "32T3K 765\nT55X5 684";
+        let Err(SolveError::Parse(err)) = aoc_common::solve::<Day07>(input, &[1]) else {
+            panic!("The input should not parse");
+        };
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.message, "`X` is not a valid card rank");
+    }
+
+    #[test]
+    fn b() {
+        let h1 = Hand::from_str("KK677").unwrap();
+        let h2 = Hand::from_str("KTJJT").unwrap();
+        assert_eq!(sort_jokers_wild(&h1, &h2), Ordering::Less);
+    }
+
+    #[test]
+    fn brute_force_jokers_find_the_best_score() {
+        let score = |hand| brute_force_jokers_wild_score(&Hand::from_str(hand).unwrap());
+        assert_eq!(score("T55J5"), Score::FourOfAKind);
+        assert_eq!(score("KTJJT"), Score::FourOfAKind);
+        assert_eq!(score("JJJJJ"), Score::FiveOfAKind);
+        assert_eq!(score("2345J"), Score::Pair);
+    }
+
+    #[test]
+    fn jokers_join_the_most_common_card() {
+        let hand = Hand::from_str("KTJJT").unwrap();
+        assert_eq!(hand.score(), Score::TwoPair);
+        assert_eq!(hand.jokers_wild_score(), Score::FourOfAKind);
+        let weaker = Hand::from_str("QQQJA").unwrap();
+        assert_eq!(weaker.cmp_jokers_wild(&hand), Ordering::Less);
+    }
+
+    #[test]
+    fn a_hand_displays_as_its_cards() {
+        assert_eq!(Hand::from_str("T2J9A").unwrap().to_string(), "T2J9A");
+    }
+
+    #[test]
+    fn the_hand_query_shows_both_scores() {
+        let bets = Day07::parse("32T3K 765\nKTJJT 220").unwrap();
+        assert_eq!(
+            repl::respond::<Day07>(&bets, &Day07::queries(), "hand 2"),
+            Ok(repl::Response::Text(
+                "KTJJT bid 220: TwoPair, FourOfAKind with jokers wild".to_string()
+            ))
+        );
+        assert!(repl::respond::<Day07>(&bets, &Day07::queries(), "hand 0").is_err());
+    }
+
+    #[test]
+    fn winnings_too_large_to_count_are_an_error() {
+        let input = format!("32T3K {}\nKTJJT 1", u64::MAX);
+        let err = aoc_common::solve::<Day07>(&input, &[1]).unwrap_err();
+        assert_eq!(
+            err.render(),
+            "day 07, part 1: The winnings are too large to count"
+        );
+        let bets = Day07::parse(&format!("32T3K 1\nKTJJT {}", u64::MAX)).unwrap();
+        assert_eq!(total_winnings(&bets), None);
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use std::cmp::Ordering;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn hand_str() -> impl Strategy<Value = String> {
+        "[23456789TJQKA]{5}"
+    }
+
+    fn hand() -> impl Strategy<Value = Hand> {
+        hand_str().prop_map(|s| s.parse::<Hand>().unwrap())
+    }
+
+    /// Bet lines with distinct hands, as the puzzle promises; with equal
+    /// hands the ranking would depend on input order.
+    fn bets() -> impl Strategy<Value = Vec<String>> {
+        prop::collection::hash_set(hand_str(), 1..30).prop_flat_map(|hands| {
+            let count = hands.len();
+            (Just(hands), prop::collection::vec(1..=1000_u64, count)).prop_map(|(hands, bids)| {
+                hands
+                    .into_iter()
+                    .zip(bids)
+                    .map(|(hand, bid)| format!("{hand} {bid}"))
+                    .collect()
+            })
+        })
+    }
+
+    fn assert_total_order<F>(a: &Hand, b: &Hand, c: &Hand, cmp: F) -> Result<(), TestCaseError>
+    where
+        F: Fn(&Hand, &Hand) -> Ordering,
+    {
+        prop_assert_eq!(cmp(a, b), cmp(b, a).reverse());
+        prop_assert_eq!(cmp(a, b) == Ordering::Equal, a == b);
+        if cmp(a, b) != Ordering::Greater && cmp(b, c) != Ordering::Greater {
+            prop_assert_ne!(cmp(a, c), Ordering::Greater);
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn hand_cmp_is_a_total_order(a in hand(), b in hand(), c in hand()) {
+            assert_total_order(&a, &b, &c, Hand::cmp)?;
+        }
+
+        #[test]
+        fn jokers_wild_is_a_total_order(a in hand(), b in hand(), c in hand()) {
+            assert_total_order(&a, &b, &c, sort_jokers_wild)?;
+        }
+
+        #[test]
+        fn sorted_hands_agree_with_cmp(mut hands in prop::collection::vec(hand(), 0..30)) {
+            hands.sort();
+            prop_assert!(hands.windows(2).all(|w| w[0].cmp(&w[1]) != Ordering::Greater));
+        }
+
+        #[test]
+        fn jokers_never_make_a_hand_worse(hand in hand()) {
+            prop_assert!(score_counts(&get_jokers_wild_best_counts(&hand)) >= hand.score());
+        }
+
+        #[test]
+        fn best_joker_counts_match_brute_force(hand in hand()) {
+            prop_assert_eq!(
+                score_counts(&get_jokers_wild_best_counts(&hand)),
+                brute_force_jokers_wild_score(&hand)
+            );
+        }
+
+        #[test]
+        fn bets_parse_to_their_hand_and_bid(hand in hand_str(), bid in any::<u64>()) {
+            let bet = format!("{hand} {bid}").parse::<Bet>().unwrap();
+            prop_assert_eq!(bet.hand, hand.parse::<Hand>().unwrap());
+            prop_assert_eq!(bet.bid, bid);
+        }
+
+        #[test]
+        fn winnings_do_not_depend_on_the_order_of_bets(
+            (lines, shuffled) in bets().prop_flat_map(|lines| {
+                (Just(lines.clone()), Just(lines).prop_shuffle())
+            })
+        ) {
+            let bets = Day07::parse(&lines.join("\n")).unwrap();
+            let shuffled = Day07::parse(&shuffled.join("\n")).unwrap();
+            prop_assert_eq!(Day07::part_one(&bets), Day07::part_one(&shuffled));
+            prop_assert_eq!(Day07::part_two(&bets), Day07::part_two(&shuffled));
+        }
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
@@ -0,0 +1,495 @@
+//! Day 8: Haunted Wasteland. A network of nodes, each with a left and a
+//! right exit, is walked by following a list of directions on repeat; part 1
+//! walks from `AAA` to `ZZZ`, part 2 walks every node ending in `A` at once
+//! until all stand on nodes ending in `Z`.
+
+#![warn(missing_docs)]
+
+use std::collections::HashMap;
+
+use aoc_common::{Alternate, Solution, SpanError};
+
+/// The [`Solution`] for day 8: the input is one [`Map`].
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = aoc_common::day_number(env!("CARGO_PKG_NAME"));
+
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Map, SpanError> {
+        let (dirs, mappings) = input.split_once("\n\n").ok_or_else(|| {
+            SpanError::at(
+                input,
+                "Directions and mappings must be separated by a blank line",
+            )
+        })?;
+        Map::new(dirs, mappings)
+    }
+
+    fn part_one(map: &Map) -> Result<String, String> {
+        map.path_steps_part_01().map(|steps| steps.to_string())
+    }
+
+    fn part_two(map: &Map) -> Result<String, String> {
+        map.path_steps_part_02().map(|steps| steps.to_string())
+    }
+
+    fn alternates() -> Vec<Alternate<Map>> {
+        vec![Alternate {
+            name: "simulation",
+            part: 2,
+            solve: |map| {
+                map.ghost_steps_simulated(MAX_SIMULATED_STEPS)
+                    .map(|steps| steps.to_string())
+            },
+        }]
+    }
+}
+
+/// Real inputs need around 10^13 steps, far more than walking them one by
+/// one can manage; the simulation gives up after this many.
+const MAX_SIMULATED_STEPS: usize = 10_000_000;
+
+/// The greatest common divisor of `x` and `y`.
+pub fn gcd(x: usize, y: usize) -> usize {
+    let mut a = x;
+    let mut b = y;
+    while b != 0 {
+        let t = b;
+        b = a % b;
+        a = t;
+    }
+    a
+}
+
+/// The least common multiple of `x` and `y`.
+pub fn lcm(x: usize, y: usize) -> usize {
+    (y / gcd(x, y)) * x
+}
+
+/// Like [`lcm`], or `None` if the multiple does not fit in a `usize`.
+pub fn checked_lcm(x: usize, y: usize) -> Option<usize> {
+    (y / gcd(x, y)).checked_mul(x)
+}
+
+/// The directions and the network of nodes.
+#[derive(Debug)]
+pub struct Map {
+    dirs: String,
+    mappings: HashMap<String, (String, String)>,
+}
+
+impl Map {
+    /// Parses the line of directions and the lines of nodes, such as
+    /// `AAA = (BBB, CCC)`. Every exit must lead to a node that has a line of
+    /// its own.
+    pub fn new(dirs: &str, ms: &str) -> Result<Map, SpanError> {
+        if let Some((i, c)) = dirs.char_indices().find(|(_, c)| !matches!(c, 'L' | 'R')) {
+            let dir = &dirs[i..i + c.len_utf8()];
+            return Err(SpanError::at(
+                dir,
+                format!("`{c}` is not a direction (L or R)"),
+            ));
+        }
+        if dirs.is_empty() {
+            return Err(SpanError::at(dirs, "No directions given"));
+        }
+
+        let mut mappings = HashMap::default();
+        let mut exits = Vec::new();
+        for line in ms.lines() {
+            let (k, m) = line.split_once(" = ").ok_or_else(|| {
+                SpanError::at(
+                    line,
+                    "Mapping must have the form <node> = (<left>, <right>)",
+                )
+            })?;
+            let (l, r) = m
+                .strip_prefix('(')
+                .and_then(|s| s.strip_suffix(')'))
+                .and_then(|s| s.split_once(", "))
+                .ok_or_else(|| {
+                    SpanError::at(m, "Destinations must have the form (<left>, <right>)")
+                })?;
+            mappings.insert(k.to_string(), (l.to_string(), r.to_string()));
+            exits.extend([l, r]);
+        }
+        if let Some(exit) = exits.iter().find(|exit| !mappings.contains_key(**exit)) {
+            return Err(SpanError::at(exit, format!("No node `{exit}` is defined")));
+        }
+        Ok(Map {
+            dirs: dirs.to_string(),
+            mappings,
+        })
+    }
+
+    /// How many steps it takes to get from `start` to a node that meets
+    /// `end_condition`, or an error if `start` is not a node. A path that
+    /// never ends never returns, unless stopped by a
+    /// [`budget`](aoc_common::budget).
+    pub fn path_steps<P>(&self, start: &str, end_condition: P) -> Result<usize, String>
+    where
+        P: Fn(&str) -> bool,
+    {
+        if !self.mappings.contains_key(start) {
+            return Err(format!("The map has no node `{start}`"));
+        }
+        let mut loc = start;
+        for (i, c) in self.dirs.chars().cycle().enumerate() {
+            aoc_common::trace!("visit", start = start, step = i, node = loc);
+            aoc_common::budget::check(|| format!("{i} steps from {start}, at {loc}"));
+            if end_condition(loc) {
+                return Ok(i);
+            }
+
+            // `Map::new` made sure every exit leads to a node
+            let mapping = &self.mappings[loc];
+            loc = match c {
+                'L' => &mapping.0,
+                'R' => &mapping.1,
+                _ => unreachable!(),
+            };
+        }
+        unreachable!("The directions repeat forever")
+    }
+
+    /// Part 1: the steps from `AAA` to `ZZZ`, or an error if the map lacks
+    /// either of them.
+    pub fn path_steps_part_01(&self) -> Result<usize, String> {
+        if let Some(node) = ["AAA", "ZZZ"]
+            .into_iter()
+            .find(|node| !self.mappings.contains_key(*node))
+        {
+            return Err(format!("The map has no node `{node}`"));
+        }
+        self.path_steps("AAA", |s| s == "ZZZ")
+    }
+
+    /// Part 2: the steps until every ghost, starting on each node ending in
+    /// `A`, stands on a node ending in `Z`. Puzzle inputs are built so each
+    /// ghost is back on its `Z` node every time as many steps again have
+    /// passed, which makes the answer the [`lcm`] of the path lengths.
+    pub fn path_steps_part_02(&self) -> Result<usize, String> {
+        self.mappings
+            .keys()
+            .filter(|k| k.ends_with('A'))
+            .map(|k| self.path_steps(k, |s| s.ends_with('Z')))
+            .try_fold(1, |multiple, steps| {
+                checked_lcm(multiple, steps?)
+                    .ok_or_else(|| "The ghosts take too many steps to count".to_string())
+            })
+    }
+
+    /// Walks every ghost at once, a step at a time, until they all stand on
+    /// a node ending in `Z`, without assuming anything about their cycles.
+    /// `None` if that takes more than `max_steps`.
+    pub fn ghost_steps_simulated(&self, max_steps: usize) -> Option<usize> {
+        let mut locs = self
+            .mappings
+            .keys()
+            .filter(|k| k.ends_with('A'))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        for (i, c) in self.dirs.chars().cycle().enumerate().take(max_steps + 1) {
+            if locs.iter().all(|loc| loc.ends_with('Z')) {
+                return Some(i);
+            }
+            aoc_common::budget::check(|| format!("{i} steps, at {}", locs.join(", ")));
+            for loc in locs.iter_mut() {
+                let mapping = &self.mappings[*loc];
+                *loc = match c {
+                    'L' => &mapping.0,
+                    'R' => &mapping.1,
+                    _ => unreachable!(),
+                };
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use aoc_common::{budget, trace::TraceFormat, SolveError};
+
+    use super::*;
+
+    mod part_01 {
+        use super::*;
+        fn assert_path_steps(dirs: &str, mappings: &str, expected_steps: usize) {
+            let map = Map::new(dirs, mappings).unwrap();
+            assert_eq!(map.path_steps_part_01(), Ok(expected_steps))
+        }
+
+        #[test]
+        fn right_path_immediately_leads_to_dest_then_path_steps_is_1() {
+            assert_path_steps("R", "AAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)", 1);
+        }
+
+        #[test]
+        fn left_path_immediately_leads_to_dest_then_path_steps_is_1() {
+            assert_path_steps("L", "AAA = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)", 1);
+        }
+
+        #[test]
+        #[allow(unused_variables)]
+        fn reach_dest_in_two_steps_single_mapping() {
+            let map = Map::new("RL", "AAA = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+        }
+
+        #[test]
+        fn reach_dest_before_end_of_dirs() {
+            assert_path_steps("RLLLR", "AAA = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)", 2);
+        }
+
+        #[test]
+        fn reach_dest_in_two_steps_two_mappings() {
+            let dirs = "LL";
+            let mappings = "AAA = (BBB, CCC)\n\
+                        BBB = (ZZZ, EEE)\n\
+                        CCC = (CCC, CCC)\n\
+                        EEE = (EEE, EEE)\n\
+                        ZZZ = (ZZZ, ZZZ)";
+            assert_path_steps(dirs, mappings, 2);
+        }
+
+        #[test]
+        fn reach_dest_in_two_steps_must_repeat_dirs() {
+            let dirs = "L";
+            let mappings = "AAA = (BBB, CCC)\n\
+                            BBB = (ZZZ, EEE)\n\
+                            CCC = (CCC, CCC)\n\
+                            EEE = (EEE, EEE)\n\
+                            ZZZ = (ZZZ, ZZZ)";
+            assert_path_steps(dirs, mappings, 2);
+        }
+
+        #[test]
+        fn every_node_visited_is_traced() {
+            let map =
+                Map::new("L", "AAA = (BBB, AAA)\nBBB = (ZZZ, AAA)\nZZZ = (ZZZ, ZZZ)").unwrap();
+            let (steps, trace) =
+                aoc_common::trace::capture(TraceFormat::Text, || map.path_steps_part_01());
+            assert_eq!(steps, Ok(2));
+            assert_eq!(
+                trace,
+                [
+                    "[day-08] visit start=AAA step=0 node=AAA",
+                    "[day-08] visit start=AAA step=1 node=BBB",
+                    "[day-08] visit start=AAA step=2 node=ZZZ",
+                ]
+            );
+        }
+
+        #[test]
+        fn reach_dest_in_example_map() {
+            let dirs = "LLR";
+            let mappings = "AAA = (BBB, BBB)\n\
+                            BBB = (AAA, ZZZ)\n\
+                            ZZZ = (ZZZ, ZZZ)";
+            assert_path_steps(dirs, mappings, 6);
+        }
+    }
+
+    mod part_02 {
+        use super::*;
+
+        #[test]
+        fn correctly_runs_example_data() {
+            let dirs = "LR";
+            let mappings = "11A = (11B, XXX)\n\
+                            11B = (XXX, 11Z)\n\
+                            11Z = (11B, XXX)\n\
+                            22A = (22B, XXX)\n\
+                            22B = (22C, 22C)\n\
+                            22C = (22Z, 22Z)\n\
+                            22Z = (22B, 22B)\n\
+                            XXX = (XXX, XXX)";
+            let map = Map::new(dirs, mappings).unwrap();
+            assert_eq!(map.path_steps_part_02(), Ok(6));
+            assert_eq!(map.ghost_steps_simulated(100), Some(6));
+        }
+
+        #[test]
+        fn simulation_gives_up_after_its_step_limit() {
+            let map =
+                Map::new("L", "11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11Z, 11Z)").unwrap();
+            assert_eq!(map.ghost_steps_simulated(1), None);
+            assert_eq!(map.ghost_steps_simulated(2), Some(2));
+        }
+    }
+
+    #[test]
+    fn part_one_needs_aaa_and_zzz() {
+        let no_aaa = "L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\nZZZ = (ZZZ, ZZZ)";
+        let no_zzz = "L\n\nAAA = (AAA, AAA)";
+        for (input, missing) in [(no_aaa, "AAA"), (no_zzz, "ZZZ")] {
+            let err = aoc_common::solve::<Day08>(input, &[1]).unwrap_err();
+            assert_eq!(
+                err.render(),
+                format!("day 08, part 1: The map has no node `{missing}`")
+            );
+        }
+        assert_eq!(
+            aoc_common::solve::<Day08>(no_aaa, &[2]).unwrap().parts[0].answer,
+            "1"
+        );
+    }
+
+    #[test]
+    fn ghost_steps_too_many_to_count_are_an_error() {
+        // Ghosts whose paths are the primes up to 53, with a product past 2^64
+        let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+        let mut input = "L\n\n".to_string();
+        for p in primes {
+            for step in 0..p {
+                let node = if step == 0 {
+                    format!("{p}A")
+                } else {
+                    format!("{p}-{step}")
+                };
+                let next = if step + 1 == p {
+                    format!("{p}Z")
+                } else {
+                    format!("{p}-{}", step + 1)
+                };
+                input += &format!("{node} = ({next}, {next})\n");
+            }
+            input += &format!("{p}Z = ({p}-1, {p}-1)\n");
+        }
+        let err = aoc_common::solve::<Day08>(&input, &[2]).unwrap_err();
+        assert_eq!(
+            err.render(),
+            "day 08, part 2: The ghosts take too many steps to count"
+        );
+    }
+
+    #[test]
+    fn lcm_is_the_smallest_common_multiple() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!([2, 3, 4].into_iter().fold(1, lcm), 12);
+        assert_eq!(checked_lcm(usize::MAX, 2), None);
+    }
+
+    #[test]
+    fn an_unreachable_zzz_times_out_with_its_progress() {
+        let input = "L\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)";
+        let timeout = budget::limit(Some(Duration::from_millis(20)), || {
+            aoc_common::solve::<Day08>(input, &[1])
+        })
+        .unwrap_err();
+        assert_eq!(timeout.part, 1);
+        assert!(timeout.progress.contains(" steps from AAA, at "));
+    }
+
+    mod parsing {
+        use super::*;
+
+        #[test]
+        fn an_unknown_direction_is_reported_where_it_appears() {
+            let input = "LRX\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+            let Err(SolveError::Parse(err)) = aoc_common::solve::<Day08>(input, &[1]) else {
+                panic!("The input should not parse");
+            };
+            assert_eq!((err.line, err.column), (1, 3));
+        }
+
+        #[test]
+        fn a_malformed_mapping_is_reported_on_its_line() {
+            let input = "L\n\nAAA = (BBB, ZZZ)\nBBB = ZZZ, ZZZ\nZZZ = (ZZZ, ZZZ)";
+            let Err(SolveError::Parse(err)) = aoc_common::solve::<Day08>(input, &[1]) else {
+                panic!("The input should not parse");
+            };
+            assert_eq!((err.line, err.column), (4, 7));
+        }
+
+        #[test]
+        fn an_exit_to_an_undefined_node_is_reported_where_it_appears() {
+            let input = "L\n\nAAA = (BBB, BBB)\nZZZ = (ZZZ, ZZZ)";
+            let Err(SolveError::Parse(err)) = aoc_common::solve::<Day08>(input, &[1]) else {
+                panic!("`BBB` is not defined");
+            };
+            assert_eq!((err.line, err.column), (3, 8));
+            assert_eq!(err.message, "No node `BBB` is defined");
+        }
+
+        #[test]
+        fn input_without_a_blank_line_cannot_be_parsed() {
+            assert!(Day08::parse("L\nAAA = (ZZZ, ZZZ)").is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// A network where following the directions from `AAA` passes through
+    /// `steps - 1` other nodes before reaching `ZZZ`. Each node's other exit
+    /// points anywhere; it is never taken on the planted path.
+    fn network() -> impl Strategy<Value = (String, String, usize)> {
+        ("[LR]{1,8}", 1..40_usize)
+            .prop_flat_map(|(dirs, steps)| {
+                let other_exits = prop::collection::vec(0..=steps, steps);
+                (Just(dirs), Just(steps), other_exits)
+            })
+            .prop_map(|(dirs, steps, other_exits)| {
+                let name = |i: usize| match i {
+                    0 => "AAA".to_string(),
+                    i if i == steps => "ZZZ".to_string(),
+                    i => format!("N{i:02}"),
+                };
+                let mut lines = (0..steps)
+                    .map(|i| {
+                        let (next, other) = (name(i + 1), name(other_exits[i]));
+                        let (left, right) = match dirs.as_bytes()[i % dirs.len()] {
+                            b'L' => (next, other),
+                            _ => (other, next),
+                        };
+                        format!("{} = ({left}, {right})", name(i))
+                    })
+                    .collect::<Vec<_>>();
+                lines.push("ZZZ = (ZZZ, ZZZ)".to_string());
+                (dirs, lines.join("\n"), steps)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn part_one_follows_the_planted_path((dirs, mappings, steps) in network()) {
+            let map = Map::new(&dirs, &mappings).unwrap();
+            prop_assert_eq!(map.path_steps_part_01(), Ok(steps));
+        }
+
+        #[test]
+        fn one_ghost_walks_the_same_path_as_part_one((dirs, mappings, _) in network()) {
+            // `AAA` is the only start and `ZZZ` the only end, so the ghosts of
+            // part 2 take exactly the part 1 route.
+            let map = Map::new(&dirs, &mappings).unwrap();
+            prop_assert_eq!(map.path_steps_part_02(), map.path_steps_part_01());
+        }
+
+        #[test]
+        fn every_mapping_line_becomes_a_node((dirs, mappings, steps) in network()) {
+            let map = Map::new(&dirs, &mappings).unwrap();
+            prop_assert_eq!(map.mappings.len(), steps + 1);
+            prop_assert_eq!(map.dirs, dirs);
+        }
+
+        #[test]
+        fn directions_other_than_l_and_r_are_rejected(dirs in "[LR]{0,5}[^LR][LR]{0,5}") {
+            prop_assert!(Map::new(&dirs, "AAA = (AAA, AAA)").is_err());
+        }
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
@@ -0,0 +1,228 @@
+//! Day 9: Mirage Maintenance. Each line is a sequence of readings, and the
+//! next (or, in part 2, the previous) value is extrapolated from the rows of
+//! differences between neighbors.
+
+#![warn(missing_docs)]
+
+use aoc_common::{Solution, SpanError};
+
+/// Part 1: the sum of each sequence's [`predict_next`], or `None` if a
+/// prediction or the sum overflows.
+pub fn sum_of_next_values(seqs: &[Vec<i32>]) -> Option<i64> {
+    seqs.iter()
+        .try_fold(0_i64, |sum, v| sum.checked_add(predict_next(v)?.into()))
+}
+
+/// Part 2: the sum of each sequence's [`predict_first`], or `None` if a
+/// prediction or the sum overflows.
+pub fn sum_of_previous_values(seqs: &[Vec<i32>]) -> Option<i64> {
+    seqs.iter()
+        .try_fold(0_i64, |sum, v| sum.checked_add(predict_first(v)?.into()))
+}
+
+/// The [`Solution`] for day 9: the input is one sequence per line.
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = aoc_common::day_number(env!("CARGO_PKG_NAME"));
+
+    type Parsed = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<i32>>, SpanError> {
+        input.lines().map(line_to_i32s).collect()
+    }
+
+    fn part_one(seqs: &Vec<Vec<i32>>) -> Result<String, String> {
+        sum_of_next_values(seqs)
+            .map(|sum| sum.to_string())
+            .ok_or_else(|| "A prediction does not fit in an i32".to_string())
+    }
+
+    fn part_two(seqs: &Vec<Vec<i32>>) -> Result<String, String> {
+        sum_of_previous_values(seqs)
+            .map(|sum| sum.to_string())
+            .ok_or_else(|| "A prediction does not fit in an i32".to_string())
+    }
+}
+
+/// Parses a line of whitespace-separated numbers.
+pub fn line_to_i32s(line: &str) -> Result<Vec<i32>, SpanError> {
+    line.split_whitespace()
+        .map(|n| {
+            n.parse::<i32>()
+                .map_err(|err| SpanError::at(n, format!("Cannot parse `{n}` as a number: {err}")))
+        })
+        .collect()
+}
+
+/// The differences between neighbors in `seq`, or `None` if one does not fit
+/// in an `i32`.
+fn differences(seq: &[i32]) -> Option<Vec<i32>> {
+    seq.windows(2).map(|d| d[1].checked_sub(d[0])).collect()
+}
+
+/// The value that comes after `seq`: its last value plus the value that
+/// comes after its differences, down to a row of zeros. `None` if it, or any
+/// difference on the way, does not fit in an `i32`.
+pub fn predict_next(seq: &[i32]) -> Option<i32> {
+    aoc_common::trace!("predict_next", row = seq);
+    if seq.iter().all(|n| *n == 0) {
+        return Some(0);
+    }
+
+    let diffs = differences(seq)?;
+    seq.last().unwrap().checked_add(predict_next(&diffs)?)
+}
+
+/// The value that comes before `seq`, worked out like [`predict_next`] at
+/// the other end.
+pub fn predict_first(seq: &[i32]) -> Option<i32> {
+    aoc_common::trace!("predict_first", row = seq);
+    if seq.iter().all(|n| *n == 0) {
+        return Some(0);
+    }
+
+    let diffs = differences(seq)?;
+    seq.first().unwrap().checked_sub(predict_first(&diffs)?)
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{trace::TraceFormat, SolveError};
+
+    use super::*;
+
+    #[test]
+    fn predict_single_zero_constant_sequence() {
+        let seq = [0];
+        assert_eq!(predict_next(&seq), Some(0));
+    }
+
+    #[test]
+    fn predict_multi_zero_constant_sequence() {
+        let seq = [0, 0, 0, 0, 0];
+        assert_eq!(predict_next(&seq), Some(0));
+    }
+
+    #[test]
+    fn predict_constant_sequence_1s() {
+        let seq = [1, 1];
+        assert_eq!(predict_next(&seq), Some(1));
+    }
+
+    #[test]
+    fn predict_monotonically_increasing_series() {
+        // 1     2     3
+        //    1     1
+        //       0
+        let seq = [1, 2, 3];
+        assert_eq!(predict_next(&seq), Some(4));
+    }
+
+    #[test]
+    fn predict_non_constant_increasing_series() {
+        let seq = [1, 3, 6, 10, 15, 21];
+        assert_eq!(predict_next(&seq), Some(28));
+    }
+
+    #[test]
+    fn predict_non_obvious_increasing_series() {
+        let seq = [10, 13, 16, 21, 30, 45];
+        assert_eq!(predict_next(&seq), Some(68));
+    }
+
+    #[test]
+    fn predict_the_first_value_in_a_series() {
+        let seq = [10, 13, 16, 21, 30, 45];
+        assert_eq!(predict_first(&seq), Some(5));
+    }
+
+    #[test]
+    fn predicting_traces_each_difference_row() {
+        let (next, trace) =
+            aoc_common::trace::capture(TraceFormat::Text, || predict_next(&[1, 2, 3]));
+        assert_eq!(next, Some(4));
+        assert_eq!(
+            trace,
+            [
+                "[day-09] predict_next row=[1, 2, 3]",
+                "[day-09] predict_next row=[1, 1]",
+                "[day-09] predict_next row=[0]",
+            ]
+        );
+    }
+
+    #[test]
+    fn differences_and_predictions_that_overflow_are_errors() {
+        assert_eq!(predict_next(&[i32::MIN, i32::MAX]), None);
+        assert_eq!(predict_next(&[i32::MAX - 1, i32::MAX]), None);
+        assert_eq!(predict_first(&[i32::MIN, i32::MIN + 1]), None);
+        let input = format!("{} {}", i32::MIN, i32::MAX);
+        let err = aoc_common::solve::<Day09>(&input, &[1]).unwrap_err();
+        assert_eq!(
+            err.render(),
+            "day 09, part 1: A prediction does not fit in an i32"
+        );
+    }
+
+    #[test]
+    fn sums_of_predictions_may_exceed_an_i32() {
+        let seqs = [vec![i32::MAX - 1, i32::MAX - 1], vec![i32::MAX - 1]];
+        assert_eq!(sum_of_next_values(&seqs), Some(2 * i64::from(i32::MAX - 1)));
+    }
+
+    #[test]
+    fn a_value_that_is_not_a_number_is_reported_where_it_appears() {
+        let input = "0 3 6\n1 3 six 10";
+        let Err(SolveError::Parse(err)) = aoc_common::solve::<Day09>(input, &[1]) else {
+            panic!("The input should not parse");
+        };
+        assert_eq!((err.line, err.column, err.width), (2, 5, 3));
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    fn eval(coefficients: &[i32], x: i32) -> i32 {
+        coefficients.iter().rev().fold(0, |acc, c| acc * x + c)
+    }
+
+    /// A polynomial of degree at most 4 with small coefficients, sampled at
+    /// enough points for its differences to reach zero.
+    fn polynomial_sequence() -> impl Strategy<Value = (Vec<i32>, Vec<i32>)> {
+        prop::collection::vec(-9..=9, 1..=5).prop_flat_map(|coefficients| {
+            let min_len = coefficients.len() + 1;
+            (Just(coefficients), min_len..=min_len + 10).prop_map(|(coefficients, len)| {
+                let seq = (0..len as i32).map(|x| eval(&coefficients, x)).collect();
+                (coefficients, seq)
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn predict_next_extends_a_polynomial((coefficients, seq) in polynomial_sequence()) {
+            prop_assert_eq!(predict_next(&seq), Some(eval(&coefficients, seq.len() as i32)));
+        }
+
+        #[test]
+        fn predict_first_extends_a_polynomial_backwards((coefficients, seq) in polynomial_sequence()) {
+            prop_assert_eq!(predict_first(&seq), Some(eval(&coefficients, -1)));
+        }
+
+        #[test]
+        fn printed_sequences_parse_back(seq in prop::collection::vec(any::<i32>(), 0..20)) {
+            let line = seq.iter().map(i32::to_string).collect::<Vec<_>>().join(" ");
+            prop_assert_eq!(line_to_i32s(&line).unwrap(), seq);
+        }
+    }
+}
+
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
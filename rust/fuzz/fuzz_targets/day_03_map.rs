@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use day_03::Day03;
+
+fuzz_target!(|input: &str| aoc_fuzz::solve::<Day03>(input));
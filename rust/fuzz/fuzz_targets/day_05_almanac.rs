@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use day_05::Day05;
+
+fuzz_target!(|input: &str| aoc_fuzz::solve::<Day05>(input));
@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use day_07::Day07;
+
+fuzz_target!(|input: &str| aoc_fuzz::solve::<Day07>(input));
@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use day_08::Day08;
+
+fuzz_target!(|input: &str| aoc_fuzz::solve::<Day08>(input));
@@ -0,0 +1,7 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use day_09::Day09;
+
+fuzz_target!(|input: &str| aoc_fuzz::solve::<Day09>(input));
@@ -0,0 +1,16 @@
+//! What every fuzz target does with its input.
+
+use std::time::Duration;
+
+use aoc_common::{budget, Solution};
+
+/// Parses `input` as a whole day's input and solves both parts, placing and
+/// rendering any error. Paths that never end are cut short by a budget, so
+/// only crashes count, not inputs that take forever.
+pub fn solve<S: Solution>(input: &str) {
+    let _ = budget::limit(Some(Duration::from_secs(1)), || {
+        if let Err(err) = aoc_common::solve::<S>(input, &[1, 2]) {
+            err.render();
+        }
+    });
+}